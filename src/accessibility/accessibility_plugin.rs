@@ -0,0 +1,14 @@
+use super::layer_symbols::*;
+use super::palette::*;
+use bevy::prelude::*;
+
+/// Colorblind palettes for the platform layers and optional symbols marking each layer.
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PaletteSettings>()
+            .add_systems(Startup, setup_layer_symbols)
+            .add_systems(Update, (apply_palette, sync_layer_symbols));
+    }
+}
@@ -0,0 +1,109 @@
+use super::palette::*;
+use crate::consts;
+use crate::environment::platform_spawner::PlatformLayer;
+use bevy::prelude::*;
+
+/// Share of a sprite's shorter side a symbol covers.
+const SYMBOL_EXTENT: f32 = 0.6;
+/// Symbols along a long platform sit this many symbol widths apart.
+const SYMBOL_SPACING: f32 = 4.0;
+
+/// Shape marking a layer: a circle for Red, a triangle for Blue and a diamond for Green.
+#[derive(Component)]
+pub struct LayerSymbol;
+
+#[derive(Resource)]
+pub struct LayerSymbolAssets {
+    circle: Handle<Mesh>,
+    triangle: Handle<Mesh>,
+    diamond: Handle<Mesh>,
+    dark: Handle<ColorMaterial>,
+    light: Handle<ColorMaterial>,
+}
+
+impl LayerSymbolAssets {
+    fn mesh(&self, layer: &PlatformLayer) -> Option<Handle<Mesh>> {
+        match layer {
+            PlatformLayer::Red => Some(self.circle.clone()),
+            PlatformLayer::Blue => Some(self.triangle.clone()),
+            PlatformLayer::Green => Some(self.diamond.clone()),
+            PlatformLayer::Default => None,
+        }
+    }
+
+    /// Dark symbols on light layer colors and light ones on dark colors.
+    fn material(&self, background: Color) -> Handle<ColorMaterial> {
+        if background.luminance() > 0.5 {
+            self.dark.clone()
+        } else {
+            self.light.clone()
+        }
+    }
+}
+
+/// Builds unit-sized symbol meshes, scaled to each sprite when spawned.
+pub fn setup_layer_symbols(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.insert_resource(LayerSymbolAssets {
+        circle: meshes.add(Circle::new(0.5)),
+        triangle: meshes.add(RegularPolygon::new(0.55, 3)),
+        diamond: meshes.add(Rhombus::new(0.8, 1.0)),
+        dark: materials.add(consts::SYMBOL_DARK),
+        light: materials.add(consts::SYMBOL_LIGHT),
+    });
+}
+
+fn spawn_symbols(
+    commands: &mut Commands,
+    assets: &LayerSymbolAssets,
+    entity: Entity,
+    visual: &LayerVisual,
+    preset: PalettePreset,
+) {
+    let Some(mesh) = assets.mesh(&visual.layer) else {
+        return;
+    };
+    let material = assets.material(preset.layer_color(&visual.layer));
+    let extent = visual.size.min_element() * SYMBOL_EXTENT;
+    // Long platforms repeat the symbol so it stays visible wherever the player stands
+    let count = ((visual.size.x / (extent * SYMBOL_SPACING)) as usize).max(1);
+    let step = visual.size.x / count as f32;
+    commands.entity(entity).with_children(|parent| {
+        for i in 0..count {
+            let x = -visual.size.x / 2.0 + step * (i as f32 + 0.5);
+            parent.spawn((
+                Mesh2d(mesh.clone()),
+                MeshMaterial2d(material.clone()),
+                Transform::from_xyz(x, 0.0, 0.1).with_scale(Vec3::new(extent, extent, 1.0)),
+                LayerSymbol,
+            ));
+        }
+    });
+}
+
+/// Adds symbols to new layer sprites while enabled, and rebuilds all of them when the
+/// palette settings change.
+pub fn sync_layer_symbols(
+    mut commands: Commands,
+    settings: Res<PaletteSettings>,
+    assets: Res<LayerSymbolAssets>,
+    visuals: Query<(Entity, Ref<LayerVisual>)>,
+    symbols: Query<Entity, With<LayerSymbol>>,
+) {
+    if settings.is_changed() {
+        for entity in symbols.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    if !settings.layer_symbols {
+        return;
+    }
+    for (entity, visual) in visuals.iter() {
+        if settings.is_changed() || visual.is_added() {
+            spawn_symbols(&mut commands, &assets, entity, &visual, settings.preset);
+        }
+    }
+}
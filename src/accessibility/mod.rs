@@ -0,0 +1,3 @@
+pub mod accessibility_plugin;
+pub mod layer_symbols;
+pub mod palette;
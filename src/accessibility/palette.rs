@@ -0,0 +1,80 @@
+use crate::consts;
+use crate::environment::platform_spawner::PlatformLayer;
+use bevy::prelude::*;
+
+/// Colors the platform layers are drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PalettePreset {
+    #[default]
+    Standard,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+    HighContrast,
+}
+
+impl PalettePreset {
+    pub fn label_key(self) -> &'static str {
+        match self {
+            PalettePreset::Standard => "palette.standard",
+            PalettePreset::Deuteranopia => "palette.deuteranopia",
+            PalettePreset::Protanopia => "palette.protanopia",
+            PalettePreset::Tritanopia => "palette.tritanopia",
+            PalettePreset::HighContrast => "palette.high_contrast",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            PalettePreset::Standard => PalettePreset::Deuteranopia,
+            PalettePreset::Deuteranopia => PalettePreset::Protanopia,
+            PalettePreset::Protanopia => PalettePreset::Tritanopia,
+            PalettePreset::Tritanopia => PalettePreset::HighContrast,
+            PalettePreset::HighContrast => PalettePreset::Standard,
+        }
+    }
+
+    pub fn layer_color(self, layer: &PlatformLayer) -> Color {
+        let colors = match self {
+            PalettePreset::Standard => return layer.get_rgb_color(),
+            PalettePreset::Deuteranopia => consts::DEUTERANOPIA_LAYERS,
+            PalettePreset::Protanopia => consts::PROTANOPIA_LAYERS,
+            PalettePreset::Tritanopia => consts::TRITANOPIA_LAYERS,
+            PalettePreset::HighContrast => consts::HIGH_CONTRAST_LAYERS,
+        };
+        match layer {
+            PlatformLayer::Red => colors[0],
+            PlatformLayer::Blue => colors[1],
+            PlatformLayer::Green => colors[2],
+            PlatformLayer::Default => layer.get_rgb_color(),
+        }
+    }
+}
+
+/// Palette for the platform layers, and whether each layer also carries a symbol so
+/// telling them apart never relies on color alone.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PaletteSettings {
+    pub preset: PalettePreset,
+    pub layer_symbols: bool,
+}
+
+/// Sprite drawn in its layer's color: platforms and crates. `size` is the sprite's
+/// size in its own local space, used to fit the layer symbols.
+#[derive(Component, Debug)]
+pub struct LayerVisual {
+    pub layer: PlatformLayer,
+    pub size: Vec2,
+}
+
+/// Recolors layer sprites when the palette changes, and new ones as they spawn.
+pub fn apply_palette(
+    settings: Res<PaletteSettings>,
+    mut query: Query<(Ref<LayerVisual>, &mut Sprite)>,
+) {
+    for (visual, mut sprite) in query.iter_mut() {
+        if settings.is_changed() || visual.is_added() {
+            sprite.color = settings.preset.layer_color(&visual.layer);
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use crate::environment::level_asset::CurrentLevel;
+use crate::GameState;
+use bevy::asset::{AssetLoadFailedEvent, LoadState};
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
+use iyes_progress::prelude::*;
 
 #[derive(AssetCollection, Resource)]
 pub struct ImageAssets {
@@ -8,3 +12,80 @@ pub struct ImageAssets {
     #[asset(path = "sprites/RTS_Crate.png")]
     pub wacky_crate: Handle<Image>,
 }
+
+/// Why the last round couldn't be loaded, shown once on the main menu.
+#[derive(Resource, Default)]
+pub struct LoadFailure(pub Option<String>);
+
+/// Loads sprite sheets through bevy_asset_loader and the selected level while in
+/// `GameState::AssetLoading`, tracking progress for the loading screen. A failed load goes
+/// back to the main menu. Headless runs skip this and insert placeholder handles instead.
+pub struct GameAssetsPlugin;
+
+impl Plugin for GameAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadFailure>()
+            .add_plugins(
+                ProgressPlugin::<GameState>::new()
+                    .with_state_transition(GameState::AssetLoading, GameState::InGame),
+            )
+            .add_loading_state(
+                LoadingState::new(GameState::AssetLoading)
+                    .load_collection::<ImageAssets>()
+                    .on_failure_continue_to_state(GameState::MainMenu),
+            )
+            .add_systems(
+                Update,
+                (
+                    level_load_progress.track_progress::<GameState>(),
+                    record_load_failures::<Image>,
+                )
+                    .run_if(in_state(GameState::AssetLoading))
+                    .after(LoadingStateSet(GameState::AssetLoading)),
+            );
+    }
+}
+
+impl ImageAssets {
+    /// Empty handles for running gameplay without a renderer to decode images.
+    pub fn placeholder() -> Self {
+        Self {
+            player: Handle::default(),
+            wacky_crate: Handle::default(),
+        }
+    }
+}
+
+/// The selected level counts as one more asset to load. The level isn't part of an asset
+/// collection, so its failure is handled here.
+pub fn level_load_progress(
+    asset_server: Res<AssetServer>,
+    level: Option<Res<CurrentLevel>>,
+    mut failure: ResMut<LoadFailure>,
+    mut game_state: ResMut<NextState<GameState>>,
+) -> Progress {
+    let Some(level) = level else {
+        return Progress { done: 0, total: 1 };
+    };
+    let done = match asset_server.get_load_state(&level.handle) {
+        Some(LoadState::Loaded) => 1,
+        Some(LoadState::Failed(error)) => {
+            failure.0 = Some(error.to_string());
+            game_state.set(GameState::MainMenu);
+            0
+        }
+        _ => 0,
+    };
+    Progress { done, total: 1 }
+}
+
+/// Keeps the error of an asset that failed to load for the main menu to show.
+pub fn record_load_failures<A: Asset>(
+    mut failed_events: EventReader<AssetLoadFailedEvent<A>>,
+    mut failure: ResMut<LoadFailure>,
+) {
+    for event in failed_events.read() {
+        warn!("{}", event.error);
+        failure.0 = Some(format!("{}: {}", event.path, event.error));
+    }
+}
@@ -1,9 +1,12 @@
 use crate::animations::player_animation_state::*;
 use crate::animations::sprite_animation::*;
+use crate::data::ron_asset::RonAsset;
 use crate::engine::player::*;
 use bevy::prelude::*;
+use serde::Deserialize;
 
-#[derive(Resource)]
+/// Frame ranges of the player sprite sheet, read from `animations/player.clips.ron`.
+#[derive(Asset, TypePath, Resource, Clone, Deserialize)]
 pub struct AnimationClips {
     pub idle_1: AnimationClip,
     pub idle_2: AnimationClip,
@@ -17,66 +20,127 @@ pub struct AnimationClips {
     pub dead: AnimationClip,
 }
 
+impl AnimationClips {
+    /// Name of the clip covering `start..=end`, for debugging.
+    pub fn name_of(&self, start: usize, end: usize) -> Option<&'static str> {
+        [
+            ("idle_1", &self.idle_1),
+            ("idle_2", &self.idle_2),
+            ("walk", &self.walk),
+            ("run", &self.run),
+            ("jump", &self.jump),
+            ("attack_1", &self.attack_1),
+            ("attack_2", &self.attack_2),
+            ("attack_3", &self.attack_3),
+            ("hurt", &self.hurt),
+            ("dead", &self.dead),
+        ]
+        .into_iter()
+        .find(|(_, clip)| clip.start == start && clip.end == end)
+        .map(|(name, _)| name)
+    }
+}
+
+impl RonAsset for AnimationClips {
+    const EXTENSIONS: &'static [&'static str] = &["clips.ron"];
+}
+
+#[derive(Clone, Deserialize)]
 pub struct AnimationClip {
     pub start: usize,
     pub end: usize,
     pub frame_duration: f32,
 }
 
-pub fn setup_animation_clips(mut commands: Commands) {
-    // Define animation indices based on your frame counts
-    commands.insert_resource(AnimationClips {
-        idle_1: AnimationClip {
-            start: 0,
-            end: 6,
-            frame_duration: 1.0 / 12.0,
-        },
-        idle_2: AnimationClip {
-            start: 7,
-            end: 19,
-            frame_duration: 1.0 / 12.0,
-        },
-        walk: AnimationClip {
-            start: 20,
-            end: 29,
-            frame_duration: 1.0 / 10.0,
-        },
-        run: AnimationClip {
-            start: 30,
-            end: 39,
-            frame_duration: 1.0 / 10.0,
-        },
-        jump: AnimationClip {
-            start: 40,
-            end: 49,
-            frame_duration: 1.0 / 10.0,
-        },
-        attack_1: AnimationClip {
-            start: 50,
-            end: 55,
-            frame_duration: 1.0 / 15.0,
-        },
-        attack_2: AnimationClip {
-            start: 56,
-            end: 59,
-            frame_duration: 1.0 / 15.0,
-        },
-        attack_3: AnimationClip {
-            start: 60,
-            end: 65,
-            frame_duration: 1.0 / 15.0,
-        },
-        hurt: AnimationClip {
-            start: 66,
-            end: 69,
-            frame_duration: 1.0 / 10.0,
-        },
-        dead: AnimationClip {
-            start: 70,
-            end: 74,
-            frame_duration: 1.0 / 5.0,
-        },
-    });
+impl Default for AnimationClips {
+    fn default() -> Self {
+        // Define animation indices based on your frame counts
+        Self {
+            idle_1: AnimationClip {
+                start: 0,
+                end: 6,
+                frame_duration: 1.0 / 12.0,
+            },
+            idle_2: AnimationClip {
+                start: 7,
+                end: 19,
+                frame_duration: 1.0 / 12.0,
+            },
+            walk: AnimationClip {
+                start: 20,
+                end: 29,
+                frame_duration: 1.0 / 10.0,
+            },
+            run: AnimationClip {
+                start: 30,
+                end: 39,
+                frame_duration: 1.0 / 10.0,
+            },
+            jump: AnimationClip {
+                start: 40,
+                end: 49,
+                frame_duration: 1.0 / 10.0,
+            },
+            attack_1: AnimationClip {
+                start: 50,
+                end: 55,
+                frame_duration: 1.0 / 15.0,
+            },
+            attack_2: AnimationClip {
+                start: 56,
+                end: 59,
+                frame_duration: 1.0 / 15.0,
+            },
+            attack_3: AnimationClip {
+                start: 60,
+                end: 65,
+                frame_duration: 1.0 / 15.0,
+            },
+            hurt: AnimationClip {
+                start: 66,
+                end: 69,
+                frame_duration: 1.0 / 10.0,
+            },
+            dead: AnimationClip {
+                start: 70,
+                end: 74,
+                frame_duration: 1.0 / 5.0,
+            },
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct AnimationClipsHandle(pub Handle<AnimationClips>);
+
+pub fn load_animation_clips(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AnimationClipsHandle(
+        asset_server.load("animations/player.clips.ron"),
+    ));
+}
+
+/// Copies edited clip definitions into `AnimationClips` and restarts the current clip on
+/// every player so the new frame ranges take effect immediately.
+pub fn sync_animation_clips(
+    mut events: EventReader<AssetEvent<AnimationClips>>,
+    handle: Res<AnimationClipsHandle>,
+    clip_assets: Res<Assets<AnimationClips>>,
+    mut clips: ResMut<AnimationClips>,
+    mut players: Query<&mut PlayerState, With<Player>>,
+) {
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            if *id != handle.0.id() {
+                continue;
+            }
+            if let Some(loaded) = clip_assets.get(*id) {
+                *clips = loaded.clone();
+                for mut state in players.iter_mut() {
+                    state.set_changed();
+                }
+            }
+        }
+    }
 }
 
 pub fn update_player_animation(
@@ -1,10 +1,12 @@
 use crate::animations::sprite_animation::*;
+use crate::data::tuning::Tuning;
+use crate::engine::gameplay_events::GameplayEvent;
 use crate::engine::player::*;
 use crate::engine::player_input::*;
 use crate::engine::player_physics::*;
 use bevy::prelude::*;
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Default)]
 pub struct PlayerState(pub Vec<PlayerStateKind>);
 
 impl PlayerState {
@@ -24,12 +26,6 @@ impl PlayerState {
     }
 }
 
-impl Default for PlayerState {
-    fn default() -> Self {
-        Self(Vec::new())
-    }
-}
-
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum PlayerStateKind {
     Idle,
@@ -52,10 +48,19 @@ pub enum PlayerStateKind {
 pub fn player_movement_state(
     mut player_move_event_reader: EventReader<PlayerInputs>,
     mut player_anim_event_reader: EventReader<AnimationEvent>,
-    mut q_player: Query<(&mut PlayerState, &mut Physics), With<Player>>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+    mut q_player: Query<(Entity, &mut PlayerState, &mut Physics), With<Player>>,
+    tuning: Res<Tuning>,
 ) {
     for ev in player_move_event_reader.read() {
-        for (mut state, mut physics) in q_player.iter_mut() {
+        // Prediction calls are handled by the game mode and don't move the player
+        if matches!(
+            ev,
+            PlayerInputs::PredictExplosion | PlayerInputs::PredictSafe
+        ) {
+            continue;
+        }
+        for (player, mut state, mut physics) in q_player.iter_mut() {
             // Clear idle state before adding a new state
             state.0.retain(|s| !matches!(s, PlayerStateKind::Idle));
             match ev {
@@ -63,8 +68,8 @@ pub fn player_movement_state(
                 PlayerInputs::Walk(direction) => {
                     if physics.on_ground {
                         let movement = match direction {
-                            MoveDirection::Left => Vec2::new(-64.0, 0.0),
-                            MoveDirection::Right => Vec2::new(64.0, 0.0),
+                            MoveDirection::Left => Vec2::new(-tuning.walk_speed, 0.0),
+                            MoveDirection::Right => Vec2::new(tuning.walk_speed, 0.0),
                         };
                         state.push_state(PlayerStateKind::Walk(movement));
                     }
@@ -73,7 +78,7 @@ pub fn player_movement_state(
                 PlayerInputs::Run => {
                     if physics.on_ground {
                         if let PlayerStateKind::Walk(vel) = state.current_state() {
-                            let run_velocity = vel * 2.0; // Increase speed
+                            let run_velocity = vel * tuning.run_multiplier; // Increase speed
                             state.push_state(PlayerStateKind::Run(run_velocity));
                         }
                     }
@@ -85,14 +90,16 @@ pub fn player_movement_state(
                             PlayerStateKind::Run(vel) | PlayerStateKind::Walk(vel) => vel,
                             _ => Vec2::ZERO,
                         };
-                        let jump_velocity = Vec2::new(jump_direction.x, 64.0); // Upward jump velocity
+                        let jump_velocity = Vec2::new(jump_direction.x, tuning.jump_velocity); // Upward jump velocity
                         state.push_state(PlayerStateKind::Jump(jump_velocity));
+                        gameplay_events.send(GameplayEvent::Jumped { player });
                     }
                 }
                 // Handle Attack State
                 PlayerInputs::Attack => {
                     if physics.on_ground {
                         state.push_state(PlayerStateKind::Attack);
+                        gameplay_events.send(GameplayEvent::Attacked { player });
                     }
                 }
                 // Handle WalkEnd and RunEnd if using event-driven approach
@@ -119,21 +126,24 @@ pub fn player_movement_state(
                     if physics.on_ground {
                         if let Some(top_state) = state.0.last_mut() {
                             if let PlayerStateKind::Run(vel) = *top_state {
-                                *top_state = PlayerStateKind::Walk(vel / 2.0);
+                                *top_state = PlayerStateKind::Walk(vel / tuning.run_multiplier);
                             }
                         }
                         physics.velocity = Vec3::ZERO;
                     }
                 }
+                PlayerInputs::PredictExplosion | PlayerInputs::PredictSafe => {}
             }
-            println!("Player State: {:#?}", state.0);
+            debug!(?player, stack = ?state.0, "player state changed");
         }
     }
     // Clean up states when finished event is triggered
     for event in player_anim_event_reader.read() {
         if let AnimationEventKind::Finished = event.kind {
-            for (mut state, mut physics) in q_player.iter_mut() {
-                if let PlayerStateKind::Attack | PlayerStateKind::Jump(_) = state.current_state() {
+            for (_, mut state, mut physics) in q_player.iter_mut() {
+                if let PlayerStateKind::Attack | PlayerStateKind::Jump(_) | PlayerStateKind::Hurt =
+                    state.current_state()
+                {
                     // Pop the Attack state from the stack
                     state.0.pop();
                     if state.0.is_empty() {
@@ -144,7 +154,6 @@ pub fn player_movement_state(
             }
         }
     }
-    //println!("Player State: {:#?}", q_player);
 }
 
 // Attack combo system
@@ -160,7 +169,7 @@ pub fn handle_attack_combo(
                 // Only allow attacking if in a valid state
                 if state.can_attack() {
                     status.attack_combo = (status.attack_combo + 1) % 3;
-                    println!("Attack Combo: {}", status.attack_combo);
+                    debug!(combo = status.attack_combo, "attack combo advanced");
                 }
             }
         }
@@ -1,7 +1,13 @@
 use crate::animations::player_animation_state::*;
+use crate::animations::sprite_animation::SpriteAnimState;
+use crate::engine::gameplay_events::GameplayEvent;
 use crate::engine::player::Player;
 use crate::engine::player_physics::*;
 use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Frames of the walk and run cycles, relative to the clip start, on which a foot lands.
+const FOOTSTEP_FRAMES: [usize; 2] = [2, 7];
 
 pub fn player_sprite_movement(
     mut query_player: Query<(&mut Transform, &mut Physics, &PlayerState), With<Player>>,
@@ -28,3 +34,28 @@ pub fn player_sprite_movement(
         }
     }
 }
+
+pub fn player_footsteps(
+    query_player: Query<(Entity, &Sprite, &SpriteAnimState, &PlayerState, &Physics), With<Player>>,
+    mut last_frames: Local<HashMap<Entity, usize>>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+) {
+    for (player, sprite, anim_state, state, physics) in query_player.iter() {
+        let Some(texture_atlas) = &sprite.texture_atlas else {
+            continue;
+        };
+        let previous = last_frames.insert(player, texture_atlas.index);
+        if previous == Some(texture_atlas.index) || !physics.on_ground {
+            continue;
+        }
+        if matches!(
+            state.current_state(),
+            PlayerStateKind::Walk(_) | PlayerStateKind::Run(_)
+        ) && FOOTSTEP_FRAMES
+            .iter()
+            .any(|frame| anim_state.start_index + frame == texture_atlas.index)
+        {
+            gameplay_events.send(GameplayEvent::Footstep { player });
+        }
+    }
+}
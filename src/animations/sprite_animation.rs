@@ -5,6 +5,7 @@ pub enum AnimationEventKind {
     _Charging,
 }
 
+/// Sent when a sprite animation reaches the end of its clip.
 #[derive(Event)]
 pub struct AnimationEvent {
     pub kind: AnimationEventKind,
@@ -37,7 +38,6 @@ pub fn animate_sprite(
         anim_state.timer.tick(time.delta());
         if anim_state.timer.finished() {
             if let Some(texture_atlas) = &mut sprite.texture_atlas {
-                //println!("Current Index: {} End Index: {}",texture_atlas.index, anim_state.end_index);
                 texture_atlas.index += 1;
                 if texture_atlas.index > anim_state.end_index {
                     texture_atlas.index = anim_state.start_index;
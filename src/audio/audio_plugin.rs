@@ -0,0 +1,23 @@
+use super::music::*;
+use super::sfx::*;
+use super::volume::*;
+use crate::GameState;
+use bevy::prelude::*;
+
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VolumeSettings>()
+            .add_systems(Startup, load_audio_assets)
+            .add_systems(
+                Update,
+                (
+                    switch_music.run_if(state_changed::<GameState>),
+                    fade_music,
+                    play_gameplay_sfx,
+                    play_ui_sfx,
+                ),
+            );
+    }
+}
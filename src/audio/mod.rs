@@ -0,0 +1,4 @@
+pub mod audio_plugin;
+pub mod music;
+pub mod sfx;
+pub mod volume;
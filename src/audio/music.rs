@@ -0,0 +1,104 @@
+use super::sfx::AudioAssets;
+use super::volume::VolumeSettings;
+use crate::GameState;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+/// Seconds it takes for one track to fade out while the next fades in.
+pub const CROSSFADE_SECS: f32 = 1.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MusicTrack {
+    Menu,
+    InGame,
+    GameOver,
+}
+
+impl MusicTrack {
+    /// Track for a state, or `None` to keep whatever is already playing.
+    pub fn for_state(state: &GameState) -> Option<Self> {
+        match state {
+            GameState::MainMenu
+            | GameState::PlayerSelection
+            | GameState::ModeSelect
+            | GameState::RuleEditor
+            | GameState::Credits
+            | GameState::Settings
+            | GameState::Stats => Some(MusicTrack::Menu),
+            GameState::AssetLoading => None,
+            GameState::InGame => Some(MusicTrack::InGame),
+            GameState::GameOver => Some(MusicTrack::GameOver),
+        }
+    }
+
+    fn handle(&self, sounds: &AudioAssets) -> Handle<AudioSource> {
+        match self {
+            MusicTrack::Menu => sounds.menu_music.clone(),
+            MusicTrack::InGame => sounds.in_game_music.clone(),
+            MusicTrack::GameOver => sounds.game_over_music.clone(),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct MusicPlayer {
+    pub track: MusicTrack,
+    /// Crossfade level in `0.0..=1.0`, multiplied with the music bus.
+    pub fade: f32,
+    pub fading_out: bool,
+}
+
+/// Starts the track for the new state and fades out everything else.
+pub fn switch_music(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    sounds: Res<AudioAssets>,
+    mut players: Query<&mut MusicPlayer>,
+) {
+    let Some(track) = MusicTrack::for_state(state.get()) else {
+        return;
+    };
+    let mut already_playing = false;
+    for mut player in players.iter_mut() {
+        if player.track == track {
+            player.fading_out = false;
+            already_playing = true;
+        } else {
+            player.fading_out = true;
+        }
+    }
+    if !already_playing {
+        commands.spawn((
+            AudioPlayer::new(track.handle(&sounds)),
+            PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+            MusicPlayer {
+                track,
+                fade: 0.0,
+                fading_out: false,
+            },
+        ));
+    }
+}
+
+pub fn fade_music(
+    mut commands: Commands,
+    mut players: Query<(Entity, &mut MusicPlayer, Option<&AudioSink>)>,
+    volume: Res<VolumeSettings>,
+    time: Res<Time<Real>>,
+) {
+    let step = time.delta_secs() / CROSSFADE_SECS;
+    for (entity, mut player, sink) in players.iter_mut() {
+        if player.fading_out {
+            player.fade = (player.fade - step).max(0.0);
+            if player.fade <= 0.0 {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+        } else {
+            player.fade = (player.fade + step).min(1.0);
+        }
+        if let Some(sink) = sink {
+            sink.set_volume(player.fade * volume.music_level());
+        }
+    }
+}
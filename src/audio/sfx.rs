@@ -0,0 +1,77 @@
+use super::volume::VolumeSettings;
+use crate::engine::gameplay_events::GameplayEvent;
+use crate::game_ui::ui_events::UiEvent;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+/// Music and sound effects. Loaded outside the asset loading state so a missing file only
+/// mutes that sound instead of blocking the game.
+#[derive(Resource)]
+pub struct AudioAssets {
+    pub menu_music: Handle<AudioSource>,
+    pub in_game_music: Handle<AudioSource>,
+    pub game_over_music: Handle<AudioSource>,
+    pub footstep: Handle<AudioSource>,
+    pub attack: Handle<AudioSource>,
+    pub crate_hit: Handle<AudioSource>,
+    pub explosion: Handle<AudioSource>,
+    pub cash_out: Handle<AudioSource>,
+    pub button_hover: Handle<AudioSource>,
+    pub button_press: Handle<AudioSource>,
+}
+
+pub fn load_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AudioAssets {
+        menu_music: asset_server.load("audio/music/menu.wav"),
+        in_game_music: asset_server.load("audio/music/in_game.wav"),
+        game_over_music: asset_server.load("audio/music/game_over.wav"),
+        footstep: asset_server.load("audio/sfx/footstep.wav"),
+        attack: asset_server.load("audio/sfx/attack.wav"),
+        crate_hit: asset_server.load("audio/sfx/crate_hit.wav"),
+        explosion: asset_server.load("audio/sfx/explosion.wav"),
+        cash_out: asset_server.load("audio/sfx/cash_out.wav"),
+        button_hover: asset_server.load("audio/sfx/button_hover.wav"),
+        button_press: asset_server.load("audio/sfx/button_press.wav"),
+    });
+}
+
+fn spawn_sfx(commands: &mut Commands, sound: &Handle<AudioSource>, volume: &VolumeSettings) {
+    commands.spawn((
+        AudioPlayer::new(sound.clone()),
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(volume.sfx_level())),
+    ));
+}
+
+pub fn play_gameplay_sfx(
+    mut commands: Commands,
+    mut gameplay_events: EventReader<GameplayEvent>,
+    sounds: Res<AudioAssets>,
+    volume: Res<VolumeSettings>,
+) {
+    for event in gameplay_events.read() {
+        let sound = match event {
+            GameplayEvent::Footstep { .. } => &sounds.footstep,
+            GameplayEvent::Attacked { .. } => &sounds.attack,
+            GameplayEvent::CrateStruck { .. } => &sounds.crate_hit,
+            GameplayEvent::CrateExploded { .. } => &sounds.explosion,
+            GameplayEvent::PotBanked { .. } => &sounds.cash_out,
+            _ => continue,
+        };
+        spawn_sfx(&mut commands, sound, &volume);
+    }
+}
+
+pub fn play_ui_sfx(
+    mut commands: Commands,
+    mut ui_events: EventReader<UiEvent>,
+    sounds: Res<AudioAssets>,
+    volume: Res<VolumeSettings>,
+) {
+    for event in ui_events.read() {
+        let sound = match event {
+            UiEvent::ButtonHovered => &sounds.button_hover,
+            UiEvent::ButtonPressed => &sounds.button_press,
+        };
+        spawn_sfx(&mut commands, sound, &volume);
+    }
+}
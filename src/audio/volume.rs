@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VolumeBus {
+    Master,
+    Music,
+    Sfx,
+}
+
+impl VolumeBus {
+    pub const ALL: [VolumeBus; 3] = [VolumeBus::Master, VolumeBus::Music, VolumeBus::Sfx];
+
+    /// String table key of the bus name.
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            VolumeBus::Master => "settings.volume.master",
+            VolumeBus::Music => "settings.volume.music",
+            VolumeBus::Sfx => "settings.volume.sfx",
+        }
+    }
+}
+
+/// Per-bus volume in `0.0..=1.0`. Music and effects are scaled by the master bus.
+#[derive(Resource, Debug)]
+pub struct VolumeSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for VolumeSettings {
+    fn default() -> Self {
+        Self {
+            master: 0.8,
+            music: 0.6,
+            sfx: 0.8,
+        }
+    }
+}
+
+impl VolumeSettings {
+    pub fn get(&self, bus: VolumeBus) -> f32 {
+        match bus {
+            VolumeBus::Master => self.master,
+            VolumeBus::Music => self.music,
+            VolumeBus::Sfx => self.sfx,
+        }
+    }
+
+    pub fn set(&mut self, bus: VolumeBus, value: f32) {
+        let level = match bus {
+            VolumeBus::Master => &mut self.master,
+            VolumeBus::Music => &mut self.music,
+            VolumeBus::Sfx => &mut self.sfx,
+        };
+        *level = value.clamp(0.0, 1.0);
+    }
+
+    pub fn adjust(&mut self, bus: VolumeBus, delta: f32) {
+        self.set(bus, self.get(bus) + delta);
+    }
+
+    pub fn music_level(&self) -> f32 {
+        self.master * self.music
+    }
+
+    pub fn sfx_level(&self) -> f32 {
+        self.master * self.sfx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjust_moves_only_the_chosen_bus() {
+        let mut volume = VolumeSettings::default();
+        volume.adjust(VolumeBus::Music, 0.1);
+        assert!((volume.music - 0.7).abs() < 1e-6);
+        assert_eq!(volume.master, 0.8);
+        assert_eq!(volume.sfx, 0.8);
+
+        volume.adjust(VolumeBus::Sfx, -0.1);
+        assert!((volume.sfx - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn adjust_clamps_to_silent_and_full() {
+        let mut volume = VolumeSettings::default();
+        for _ in 0..20 {
+            volume.adjust(VolumeBus::Master, 0.1);
+        }
+        assert_eq!(volume.get(VolumeBus::Master), 1.0);
+        for _ in 0..20 {
+            volume.adjust(VolumeBus::Master, -0.1);
+        }
+        assert_eq!(volume.get(VolumeBus::Master), 0.0);
+    }
+
+    #[test]
+    fn master_scales_music_and_effects() {
+        let volume = VolumeSettings {
+            master: 0.5,
+            music: 0.6,
+            sfx: 1.0,
+        };
+        assert!((volume.music_level() - 0.3).abs() < 1e-6);
+        assert_eq!(volume.sfx_level(), 0.5);
+    }
+}
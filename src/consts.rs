@@ -1,14 +1,54 @@
 use bevy::prelude::*;
 
-pub const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
-pub const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
 pub const _INPUT_FIELD_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
-pub const _INPUT_FIELD_BG: Color = Color::srgb(0.50, 0.75, 0.40);
-pub const WHITE: Color = Color::srgb(0.00, 0.00, 0.00);
-pub const BUTTON_BORDER: Color = Color::srgb(0.80, 0.10, 0.40);
+pub const INPUT_FIELD_BG: Color = Color::srgb(0.50, 0.75, 0.40);
+pub const BLACK: Color = Color::srgb(0.00, 0.00, 0.00);
+pub const FOCUS_OUTLINE: Color = Color::srgb(1.00, 0.85, 0.20);
 pub const TEXT_COLOR: Color = Color::srgb(0.10, 0.80, 0.60);
+pub const OVERLAY_BG: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
 
+pub const THEME_TEXT: Color = Color::srgb(1.00, 0.94, 0.82);
+pub const THEME_BUTTON_TEXT: Color = Color::srgb(0.35, 0.20, 0.10);
+pub const LOAD_ERROR: Color = Color::srgb(1.00, 0.45, 0.35);
+// Multiplied into widget images while hovered or pressed
+pub const HOVERED_TINT: Color = Color::srgb(0.88, 0.88, 0.88);
+pub const PRESSED_TINT: Color = Color::srgb(0.72, 0.72, 0.72);
 
 pub const RED: Color = Color::srgb(1.0, 0.0, 0.0);
-pub const BLUE: Color = Color::srgb(0.0, 1.0, 0.0);
-pub const GREEN: Color = Color::srgb(0.0, 0.0, 1.0);
\ No newline at end of file
+pub const BLUE: Color = Color::srgb(0.0, 0.0, 1.0);
+pub const GREEN: Color = Color::srgb(0.0, 1.0, 0.0);
+
+// Layer colors for each colorblind preset, in Red, Blue, Green layer order. Picked from
+// the Okabe-Ito palette so the three stay apart in lightness as well as hue.
+pub const DEUTERANOPIA_LAYERS: [Color; 3] = [
+    Color::srgb(0.84, 0.37, 0.00),
+    Color::srgb(0.00, 0.45, 0.70),
+    Color::srgb(0.94, 0.89, 0.26),
+];
+pub const PROTANOPIA_LAYERS: [Color; 3] = [
+    Color::srgb(0.90, 0.62, 0.00),
+    Color::srgb(0.00, 0.45, 0.70),
+    Color::srgb(0.94, 0.89, 0.26),
+];
+pub const TRITANOPIA_LAYERS: [Color; 3] = [
+    Color::srgb(0.84, 0.15, 0.15),
+    Color::srgb(0.00, 0.62, 0.65),
+    Color::srgb(0.90, 0.90, 0.90),
+];
+pub const HIGH_CONTRAST_LAYERS: [Color; 3] = [
+    Color::srgb(1.00, 1.00, 1.00),
+    Color::srgb(1.00, 0.90, 0.00),
+    Color::srgb(0.00, 0.90, 1.00),
+];
+pub const SYMBOL_DARK: Color = Color::srgba(0.05, 0.05, 0.05, 0.85);
+pub const SYMBOL_LIGHT: Color = Color::srgba(0.95, 0.95, 0.95, 0.85);
+
+pub const HAZARD: Color = Color::srgb(0.30, 0.05, 0.05);
+pub const CASH_OUT: Color = Color::srgba(0.95, 0.80, 0.20, 0.6);
+
+pub const PREDICTED_BAR: Color = Color::srgb(0.95, 0.75, 0.20);
+pub const OBSERVED_BAR: Color = Color::srgb(0.20, 0.60, 0.95);
+pub const HISTOGRAM_BAR: Color = Color::srgb(0.10, 0.80, 0.60);
+
+pub const PICTOGRAM_EXPLODE: Color = Color::srgb(0.95, 0.25, 0.15);
+pub const PICTOGRAM_SAFE: Color = Color::srgb(0.55, 0.55, 0.55);
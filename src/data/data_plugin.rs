@@ -0,0 +1,16 @@
+use super::ron_asset::*;
+use super::rule_set::SelectedRules;
+use super::tuning::*;
+use bevy::prelude::*;
+
+pub struct DataPlugin;
+
+impl Plugin for DataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_ron_asset::<Tuning>()
+            .init_resource::<Tuning>()
+            .init_resource::<SelectedRules>()
+            .add_systems(Startup, load_tuning)
+            .add_systems(Update, sync_tuning);
+    }
+}
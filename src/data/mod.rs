@@ -0,0 +1,4 @@
+pub mod data_plugin;
+pub mod ron_asset;
+pub mod rule_set;
+pub mod tuning;
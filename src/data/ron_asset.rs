@@ -0,0 +1,59 @@
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use thiserror::Error;
+
+/// An asset stored as a RON file, identified by its compound extension (e.g. `level.ron`).
+pub trait RonAsset: Asset + DeserializeOwned {
+    const EXTENSIONS: &'static [&'static str];
+}
+
+pub struct RonAssetLoader<A: RonAsset>(PhantomData<A>);
+
+impl<A: RonAsset> Default for RonAssetLoader<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum RonAssetLoaderError {
+    #[error("Could not read data file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse data file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl<A: RonAsset> AssetLoader for RonAssetLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = RonAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes::<A>(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        A::EXTENSIONS
+    }
+}
+
+pub trait RonAssetAppExt {
+    fn init_ron_asset<A: RonAsset>(&mut self) -> &mut Self;
+}
+
+impl RonAssetAppExt for App {
+    fn init_ron_asset<A: RonAsset>(&mut self) -> &mut Self {
+        self.init_asset::<A>()
+            .register_asset_loader(RonAssetLoader::<A>::default())
+    }
+}
@@ -0,0 +1,258 @@
+use super::tuning::Tuning;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Directory, relative to the working directory, where custom rule sets are saved.
+pub const RULE_SET_DIR: &str = "rule_sets";
+pub const RULE_SET_EXTENSION: &str = "rules.ron";
+
+/// The gameplay numbers a teacher can change, e.g. to build an exercise around one
+/// explosion chance. Applied on top of the tuning file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RuleSet {
+    pub name: String,
+    pub player_gravity: f32,
+    /// Gravity of the crates.
+    pub world_gravity: f32,
+    pub walk_speed: f32,
+    pub run_multiplier: f32,
+    pub jump_velocity: f32,
+    /// How many times faster than normal crates spawn.
+    pub spawn_rate: f32,
+    /// Explosion chance of the first crate.
+    pub explosion_base: f32,
+    /// Highest explosion chance the odds climb to.
+    pub explosion_max: f32,
+    pub hp: u32,
+    /// Hp an explosion takes.
+    pub damage: u32,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum RuleSetError {
+    #[error("Could not access rule set file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse rule set file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Could not write rule set file: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl RuleSet {
+    /// The rules `tuning` plays with.
+    pub fn from_tuning(tuning: &Tuning) -> Self {
+        Self {
+            name: String::new(),
+            player_gravity: tuning.player_gravity,
+            world_gravity: tuning.world_gravity,
+            walk_speed: tuning.walk_speed,
+            run_multiplier: tuning.run_multiplier,
+            jump_velocity: tuning.jump_velocity,
+            spawn_rate: tuning.spawn_rate,
+            explosion_base: tuning.explosion.base,
+            explosion_max: tuning.explosion.max,
+            hp: tuning.player_hp,
+            damage: tuning.explosion_damage,
+        }
+    }
+
+    pub fn apply_to(&self, tuning: &mut Tuning) {
+        tuning.player_gravity = self.player_gravity;
+        tuning.world_gravity = self.world_gravity;
+        tuning.walk_speed = self.walk_speed;
+        tuning.run_multiplier = self.run_multiplier;
+        tuning.jump_velocity = self.jump_velocity;
+        tuning.spawn_rate = self.spawn_rate;
+        tuning.explosion.base = self.explosion_base;
+        tuning.explosion.max = self.explosion_max;
+        tuning.player_hp = self.hp;
+        tuning.explosion_damage = self.damage;
+    }
+
+    /// Writes the rule set to `rule_sets/<name>.rules.ron`, replacing any set of the same
+    /// name, and returns its path.
+    pub fn save(&self) -> Result<PathBuf, RuleSetError> {
+        std::fs::create_dir_all(RULE_SET_DIR)?;
+        // Names are typed as letters, digits and spaces
+        let file_name = self.name.trim().replace(' ', "_");
+        let path = Path::new(RULE_SET_DIR).join(format!("{file_name}.{RULE_SET_EXTENSION}"));
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, RuleSetError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    /// Every saved rule set that could be read, sorted by name.
+    pub fn load_all() -> Vec<Self> {
+        let Ok(entries) = std::fs::read_dir(RULE_SET_DIR) else {
+            return Vec::new();
+        };
+        let mut rule_sets: Vec<Self> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(RULE_SET_EXTENSION)
+            })
+            .filter_map(|entry| {
+                Self::load(&entry.path())
+                    .inspect_err(|error| warn!("{error}"))
+                    .ok()
+            })
+            .collect();
+        rule_sets.sort_by(|a, b| a.name.cmp(&b.name));
+        rule_sets
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DifficultyPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl DifficultyPreset {
+    pub const ALL: [DifficultyPreset; 3] = [
+        DifficultyPreset::Easy,
+        DifficultyPreset::Normal,
+        DifficultyPreset::Hard,
+    ];
+
+    /// String table key of the preset name.
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "rules.easy",
+            DifficultyPreset::Normal => "rules.normal",
+            DifficultyPreset::Hard => "rules.hard",
+        }
+    }
+
+    /// The preset's rules, scaled from `base` so that Normal plays the tuning file as is.
+    pub fn rules(&self, base: &Tuning) -> RuleSet {
+        let mut rules = RuleSet::from_tuning(base);
+        match self {
+            DifficultyPreset::Easy => {
+                rules.spawn_rate *= 0.75;
+                rules.explosion_base *= 0.5;
+                rules.explosion_max *= 0.6;
+                rules.hp = rules.hp * 3 / 2;
+                rules.damage /= 2;
+            }
+            DifficultyPreset::Normal => {}
+            DifficultyPreset::Hard => {
+                rules.spawn_rate *= 1.5;
+                rules.explosion_base *= 2.0;
+                // Halfway from the normal cap to a sure explosion
+                rules.explosion_max = (rules.explosion_max + 1.0) / 2.0;
+                rules.hp = rules.hp * 7 / 10;
+                rules.damage = rules.damage * 3 / 2;
+            }
+        }
+        rules
+    }
+}
+
+/// Rules a round is played with: a preset, or a custom rule set.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum RuleChoice {
+    Preset(DifficultyPreset),
+    Custom(RuleSet),
+}
+
+impl Default for RuleChoice {
+    fn default() -> Self {
+        RuleChoice::Preset(DifficultyPreset::Normal)
+    }
+}
+
+impl RuleChoice {
+    pub fn rules(&self, base: &Tuning) -> RuleSet {
+        match self {
+            RuleChoice::Preset(preset) => preset.rules(base),
+            RuleChoice::Custom(rules) => rules.clone(),
+        }
+    }
+}
+
+/// Rules the next round is played with, picked on the mode select screen.
+#[derive(Resource, Default)]
+pub struct SelectedRules(pub RuleChoice);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::tuning::ExplosionCurve;
+
+    fn base() -> Tuning {
+        let tuning = Tuning::default();
+        Tuning {
+            spawn_rate: 1.0,
+            explosion: ExplosionCurve {
+                base: 0.1,
+                max: 0.6,
+                ..tuning.explosion
+            },
+            player_hp: 100,
+            explosion_damage: 20,
+            ..tuning
+        }
+    }
+
+    #[test]
+    fn normal_plays_the_tuning_file() {
+        let base = base();
+        assert_eq!(
+            DifficultyPreset::Normal.rules(&base),
+            RuleSet::from_tuning(&base)
+        );
+    }
+
+    #[test]
+    fn easy_is_gentler() {
+        let rules = DifficultyPreset::Easy.rules(&base());
+        assert_eq!(rules.spawn_rate, 0.75);
+        assert_eq!(rules.explosion_base, 0.05);
+        assert!((rules.explosion_max - 0.36).abs() < 1e-6);
+        assert_eq!(rules.hp, 150);
+        assert_eq!(rules.damage, 10);
+    }
+
+    #[test]
+    fn hard_is_harsher_but_never_certain() {
+        let rules = DifficultyPreset::Hard.rules(&base());
+        assert_eq!(rules.spawn_rate, 1.5);
+        assert_eq!(rules.explosion_base, 0.2);
+        assert!((rules.explosion_max - 0.8).abs() < 1e-6);
+        assert_eq!(rules.hp, 70);
+        assert_eq!(rules.damage, 30);
+    }
+
+    #[test]
+    fn presets_keep_the_other_rules() {
+        let base = base();
+        for preset in DifficultyPreset::ALL {
+            let rules = preset.rules(&base);
+            assert_eq!(rules.walk_speed, base.walk_speed);
+            assert_eq!(rules.jump_velocity, base.jump_velocity);
+            assert_eq!(rules.player_gravity, base.player_gravity);
+        }
+    }
+
+    #[test]
+    fn rules_applied_to_tuning_read_back_the_same() {
+        let rules = DifficultyPreset::Hard.rules(&base());
+        let mut tuning = base();
+        rules.apply_to(&mut tuning);
+        assert_eq!(RuleSet::from_tuning(&tuning), rules);
+    }
+}
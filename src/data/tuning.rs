@@ -0,0 +1,209 @@
+use super::ron_asset::RonAsset;
+use super::rule_set::SelectedRules;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Gameplay numbers designers are expected to tweak, read from `tuning/gameplay.tuning.ron`.
+///
+/// The resource starts out with the built-in defaults and is replaced whenever the asset
+/// is loaded or edited on disk, with the selected rule set applied on top.
+#[derive(Asset, TypePath, Resource, Clone, Debug, Deserialize)]
+pub struct Tuning {
+    /// Downward acceleration applied to the player while airborne.
+    pub player_gravity: f32,
+    /// Gravity handed to avian2d for crates and other rigid bodies.
+    pub world_gravity: f32,
+    pub walk_speed: f32,
+    pub run_multiplier: f32,
+    pub jump_velocity: f32,
+    /// Multiplies how often crates spawn; 1 keeps each round's random interval.
+    pub spawn_rate: f32,
+    pub explosion: ExplosionCurve,
+    /// Hp the player starts a round with, before any assist extra hp.
+    pub player_hp: u32,
+    /// Hp an exploding crate takes.
+    pub explosion_damage: u32,
+    pub time_attack: TimeAttackRules,
+    pub survival: SurvivalRules,
+    /// How long gameplay freezes when a strike connects.
+    pub hit_stop_secs: f32,
+    /// How long a damaged sprite stays tinted.
+    pub flash_secs: f32,
+    /// Invulnerability after being hurt; never shorter than the hurt clip.
+    pub invulnerability_secs: f32,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            player_gravity: -981.0,
+            world_gravity: -327.0,
+            walk_speed: 64.0,
+            run_multiplier: 2.0,
+            jump_velocity: 64.0,
+            spawn_rate: 1.0,
+            explosion: ExplosionCurve::default(),
+            player_hp: 100,
+            explosion_damage: 20,
+            time_attack: TimeAttackRules::default(),
+            survival: SurvivalRules::default(),
+            hit_stop_secs: 0.06,
+            flash_secs: 0.15,
+            invulnerability_secs: 1.0,
+        }
+    }
+}
+
+impl RonAsset for Tuning {
+    const EXTENSIONS: &'static [&'static str] = &["tuning.ron"];
+}
+
+/// Explosion probability of the n-th crate in a round: `base + step * n`, capped at `max`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExplosionCurve {
+    pub base: f32,
+    pub step: f32,
+    pub max: f32,
+}
+
+impl Default for ExplosionCurve {
+    fn default() -> Self {
+        Self {
+            base: 0.05,
+            step: 0.03,
+            max: 0.9,
+        }
+    }
+}
+
+impl ExplosionCurve {
+    pub fn chance_for(&self, crate_index: u32) -> f32 {
+        (self.base + self.step * crate_index as f32).clamp(0.0, self.max.min(1.0))
+    }
+
+    /// How far `step` is from the base chance towards the cap, from 0 to 1.
+    pub fn progress(&self, step: u32) -> f32 {
+        let range = self.max.min(1.0) - self.base;
+        if range <= 0.0 {
+            return 1.0;
+        }
+        ((self.chance_for(step) - self.base) / range).clamp(0.0, 1.0)
+    }
+}
+
+/// Clock of a Time Attack round. Instead of rising with every crate, the explosion curve
+/// goes up one step every `step_secs` of play.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TimeAttackRules {
+    pub round_secs: f32,
+    pub step_secs: f32,
+}
+
+impl Default for TimeAttackRules {
+    fn default() -> Self {
+        Self {
+            round_secs: 90.0,
+            step_secs: 5.0,
+        }
+    }
+}
+
+/// Difficulty of a Survival round, which climbs with the score: one step up the explosion
+/// curve every `points_per_step` banked points. Each step also adds `spawn_rate_step` to
+/// the spawn rate, where 1 is the round's base rate, up to `max_spawn_rate`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SurvivalRules {
+    pub points_per_step: u32,
+    pub spawn_rate_step: f32,
+    pub max_spawn_rate: f32,
+}
+
+impl Default for SurvivalRules {
+    fn default() -> Self {
+        Self {
+            points_per_step: 5,
+            spawn_rate_step: 0.15,
+            max_spawn_rate: 4.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct TuningHandle(pub Handle<Tuning>);
+
+pub fn load_tuning(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(TuningHandle(
+        asset_server.load("tuning/gameplay.tuning.ron"),
+    ));
+}
+
+/// Copies the tuning asset into the `Tuning` resource on first load, on every edit and
+/// whenever another rule set is selected.
+pub fn sync_tuning(
+    mut events: EventReader<AssetEvent<Tuning>>,
+    tuning_assets: Res<Assets<Tuning>>,
+    handle: Res<TuningHandle>,
+    selected: Res<SelectedRules>,
+    mut tuning: ResMut<Tuning>,
+) {
+    let asset_changed = events
+        .read()
+        .filter(|event| {
+            matches!(
+                event,
+                AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }
+                    if *id == handle.0.id()
+            )
+        })
+        .count()
+        > 0;
+    if !asset_changed && !selected.is_changed() {
+        return;
+    }
+    // Rules picked before the file has loaded apply to the defaults until it does
+    let mut synced = tuning_assets.get(&handle.0).cloned().unwrap_or_default();
+    selected.0.rules(&synced).apply_to(&mut synced);
+    *tuning = synced;
+    info!("Applied gameplay tuning");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve() -> ExplosionCurve {
+        ExplosionCurve {
+            base: 0.1,
+            step: 0.2,
+            max: 0.6,
+        }
+    }
+
+    #[test]
+    fn first_crate_gets_the_base_chance() {
+        assert_eq!(curve().chance_for(0), 0.1);
+    }
+
+    #[test]
+    fn each_crate_adds_one_step() {
+        let curve = curve();
+        assert!((curve.chance_for(1) - 0.3).abs() < 1e-6);
+        assert!((curve.chance_for(2) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn chance_stops_at_the_cap() {
+        let curve = curve();
+        assert_eq!(curve.chance_for(3), 0.6);
+        assert_eq!(curve.chance_for(1000), 0.6);
+    }
+
+    #[test]
+    fn cap_above_one_still_stops_at_certainty() {
+        let curve = ExplosionCurve {
+            max: 1.5,
+            ..curve()
+        };
+        assert_eq!(curve.chance_for(1000), 1.0);
+    }
+}
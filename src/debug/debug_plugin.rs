@@ -0,0 +1,24 @@
+use super::inspector::*;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::EguiPlugin;
+
+/// Developer overlay: press F3 to inspect the player and crates, show collider gizmos
+/// and spawn or detonate crates on demand. Needs a window, so it isn't part of headless runs.
+pub struct DebugInspectorPlugin;
+
+impl Plugin for DebugInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((EguiPlugin, PhysicsDebugPlugin::default()))
+            .init_resource::<DebugInspector>()
+            .add_systems(
+                Update,
+                (
+                    toggle_inspector,
+                    sync_physics_gizmos.run_if(resource_changed::<DebugInspector>),
+                    inspector_window.run_if(|inspector: Res<DebugInspector>| inspector.visible),
+                )
+                    .chain(),
+            );
+    }
+}
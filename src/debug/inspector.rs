@@ -0,0 +1,205 @@
+use crate::animations::asset_loader::ImageAssets;
+use crate::animations::player_animation_clips::AnimationClips;
+use crate::animations::player_animation_state::PlayerState;
+use crate::animations::sprite_animation::SpriteAnimState;
+use crate::data::tuning::Tuning;
+use crate::engine::crate_strike::ForceExplosion;
+use crate::engine::debug_action::DebugAction;
+use crate::engine::difficulty::DifficultyCurve;
+use crate::engine::player::{Player, PlayerStatus};
+use crate::engine::player_physics::Physics;
+use crate::engine::time_attack::RoundClock;
+use crate::environment::crate_spawner::*;
+use crate::environment::environment_plugin::SelectionTimer;
+use crate::environment::platform_spawner::PlatformLayer;
+use crate::replay::playback::Playback;
+use crate::{GameState, InGameState};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+/// How far above the player crates spawned from the inspector appear.
+const DEBUG_SPAWN_HEIGHT: f32 = 150.0;
+
+/// Developer overlay state. Toggled with F3.
+#[derive(Resource)]
+pub struct DebugInspector {
+    pub visible: bool,
+    pub spawn_color: PlatformLayer,
+}
+
+impl Default for DebugInspector {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            spawn_color: PlatformLayer::Red,
+        }
+    }
+}
+
+pub fn toggle_inspector(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut inspector: ResMut<DebugInspector>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F3) {
+        inspector.visible = !inspector.visible;
+    }
+}
+
+/// Shows avian's collider outlines only while the inspector is open.
+pub fn sync_physics_gizmos(
+    inspector: Res<DebugInspector>,
+    mut config_store: ResMut<GizmoConfigStore>,
+) {
+    config_store.config_mut::<PhysicsGizmos>().0.enabled = inspector.visible;
+}
+
+pub fn inspector_window(
+    mut contexts: EguiContexts,
+    mut inspector: ResMut<DebugInspector>,
+    game_state: Res<State<GameState>>,
+    in_game_state: Option<Res<State<InGameState>>>,
+    players: Query<
+        (
+            &Transform,
+            &PlayerState,
+            &Physics,
+            &PlayerStatus,
+            &Sprite,
+            &SpriteAnimState,
+        ),
+        With<Player>,
+    >,
+    crates: Query<&CrateRisk, With<WackyCrate>>,
+    clips: Res<AnimationClips>,
+    spawn_timer: Res<SelectionTimer>,
+    image_assets: Option<Res<ImageAssets>>,
+    tuning: Res<Tuning>,
+    crates_spawned: Res<CratesSpawned>,
+    clock: Option<Res<RoundClock>>,
+    difficulty: Res<DifficultyCurve>,
+    force_explosion: Res<ForceExplosion>,
+    mut debug_actions: EventWriter<DebugAction>,
+    playback: Option<Res<Playback>>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    egui::Window::new("Inspector").show(ctx, |ui| {
+        ui.label(format!("Game state: {:?}", game_state.get()));
+        if let Some(in_game_state) = &in_game_state {
+            ui.label(format!("Round: {:?}", in_game_state.get()));
+        }
+
+        for (transform, state, physics, status, sprite, anim_state) in players.iter() {
+            ui.separator();
+            ui.heading("Player");
+            ui.label(format!(
+                "Position: ({:.1}, {:.1})",
+                transform.translation.x, transform.translation.y
+            ));
+            ui.label(format!(
+                "HP: {}  Points: {}  Pot: {}  Streak: {}",
+                status.hp, status.points, status.pot, status.streak
+            ));
+            ui.label("State stack (top last):");
+            for kind in state.0.iter() {
+                ui.monospace(format!("  {kind:?}"));
+            }
+            ui.label(format!(
+                "Velocity: ({:.1}, {:.1})  Acceleration: ({:.1}, {:.1})",
+                physics.velocity.x,
+                physics.velocity.y,
+                physics.acceleration.x,
+                physics.acceleration.y
+            ));
+            ui.label(format!("On ground: {}", physics.on_ground));
+            let clip = clips
+                .name_of(anim_state.start_index, anim_state.end_index)
+                .unwrap_or("?");
+            let frame = sprite
+                .texture_atlas
+                .as_ref()
+                .map_or(0, |texture_atlas| texture_atlas.index);
+            ui.label(format!(
+                "Clip: {clip} ({}..={})  Frame: {frame}",
+                anim_state.start_index, anim_state.end_index
+            ));
+        }
+
+        ui.separator();
+        ui.heading("Crates");
+        ui.label(format!(
+            "In play: {}  Spawned this round: {}",
+            crates.iter().count(),
+            crates_spawned.0
+        ));
+        ui.label(format!(
+            "Next spawn in {:.2}s of {:.2}s",
+            spawn_timer.0.remaining_secs(),
+            spawn_timer.0.duration().as_secs_f32()
+        ));
+        ui.label(format!(
+            "Difficulty: {:?}, step {}, spawn rate x{:.2}",
+            difficulty.pace,
+            difficulty.step,
+            difficulty.spawn_rate(&tuning.survival)
+        ));
+        if let Some(clock) = &clock {
+            ui.label(format!(
+                "Time Attack: {:.1}s left",
+                clock.0.remaining_secs()
+            ));
+        }
+        let mut risks: Vec<&CrateRisk> = crates.iter().collect();
+        risks.sort_by_key(|risk| risk.index);
+        for risk in risks {
+            ui.monospace(format!(
+                "#{:<3} {:>5.1}% explode",
+                risk.index,
+                risk.explosion_chance * 100.0
+            ));
+        }
+
+        ui.separator();
+        ui.heading("Controls");
+        ui.horizontal(|ui| {
+            for color in [
+                PlatformLayer::Red,
+                PlatformLayer::Blue,
+                PlatformLayer::Green,
+            ] {
+                let label = format!("{color:?}");
+                ui.selectable_value(&mut inspector.spawn_color, color, label);
+            }
+        });
+        let player_position = players
+            .iter()
+            .next()
+            .map(|(transform, ..)| transform.translation.truncate());
+        // A replay already holds the changes made while it was recorded
+        let editable = playback.is_none();
+        let can_spawn = editable && image_assets.is_some() && player_position.is_some();
+        if ui
+            .add_enabled(can_spawn, egui::Button::new("Spawn crate above player"))
+            .clicked()
+        {
+            if let Some(position) = player_position {
+                debug_actions.send(DebugAction::SpawnCrate {
+                    position: position + Vec2::Y * DEBUG_SPAWN_HEIGHT,
+                    layer: inspector.spawn_color.clone(),
+                });
+            }
+        }
+        let mut forced = force_explosion.0;
+        if ui
+            .add_enabled(
+                editable,
+                egui::Checkbox::new(&mut forced, "Next struck crate explodes"),
+            )
+            .changed()
+        {
+            debug_actions.send(DebugAction::ForceExplosion(forced));
+        }
+    });
+}
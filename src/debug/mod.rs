@@ -0,0 +1,2 @@
+pub mod debug_plugin;
+pub mod inspector;
@@ -0,0 +1,32 @@
+use super::particles::*;
+use super::presets::*;
+use crate::{GameState, InGameState};
+use bevy::prelude::*;
+
+pub struct EffectsPlugin;
+
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_gameplay_effects,
+                emit_particles,
+                update_particles,
+                update_floating_text,
+            )
+                .chain()
+                .run_if(in_state(InGameState::Running)),
+        )
+        .add_systems(OnExit(GameState::InGame), despawn_effects);
+    }
+}
+
+fn despawn_effects(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<ParticleEmitter>, With<Particle>, With<FloatingText>)>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
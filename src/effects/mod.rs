@@ -0,0 +1,3 @@
+pub mod effects_plugin;
+pub mod particles;
+pub mod presets;
@@ -0,0 +1,150 @@
+use bevy::color::Mix;
+use bevy::prelude::*;
+use std::ops::Range;
+
+/// Describes the particles an emitter produces.
+#[derive(Clone, Debug)]
+pub struct EmitterConfig {
+    /// Particles released on the emitter's first frame.
+    pub burst: u32,
+    /// Particles per second released for the rest of the emitter's lifetime.
+    pub rate: f32,
+    /// How long the emitter keeps emitting, in seconds.
+    pub duration: f32,
+    pub particle_lifetime: Range<f32>,
+    pub speed: Range<f32>,
+    /// Launch direction in radians, counter-clockwise from +x.
+    pub angle: Range<f32>,
+    pub gravity: f32,
+    pub start_color: LinearRgba,
+    pub end_color: LinearRgba,
+    pub start_size: f32,
+    pub end_size: f32,
+}
+
+#[derive(Component)]
+pub struct ParticleEmitter {
+    pub config: EmitterConfig,
+    pub timer: Timer,
+    pub started: bool,
+    pending: f32,
+}
+
+impl ParticleEmitter {
+    pub fn new(config: EmitterConfig) -> Self {
+        Self {
+            timer: Timer::from_seconds(config.duration, TimerMode::Once),
+            config,
+            started: false,
+            pending: 0.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec2,
+    pub gravity: f32,
+    pub lifetime: Timer,
+    pub start_color: LinearRgba,
+    pub end_color: LinearRgba,
+    pub start_size: f32,
+    pub end_size: f32,
+}
+
+/// World-space text that drifts upwards and fades out, e.g. score popups.
+#[derive(Component)]
+pub struct FloatingText {
+    pub velocity: Vec2,
+    pub lifetime: Timer,
+}
+
+fn random_in(range: &Range<f32>) -> f32 {
+    if range.start < range.end {
+        rand::random_range(range.clone())
+    } else {
+        range.start
+    }
+}
+
+fn spawn_particle(commands: &mut Commands, config: &EmitterConfig, origin: Vec3) {
+    let angle = random_in(&config.angle);
+    let speed = random_in(&config.speed);
+    commands.spawn((
+        Sprite {
+            color: config.start_color.into(),
+            custom_size: Some(Vec2::splat(config.start_size)),
+            ..default()
+        },
+        Transform::from_translation(origin),
+        Particle {
+            velocity: Vec2::from_angle(angle) * speed,
+            gravity: config.gravity,
+            lifetime: Timer::from_seconds(random_in(&config.particle_lifetime), TimerMode::Once),
+            start_color: config.start_color,
+            end_color: config.end_color,
+            start_size: config.start_size,
+            end_size: config.end_size,
+        },
+    ));
+}
+
+pub fn emit_particles(
+    mut commands: Commands,
+    mut emitters: Query<(Entity, &Transform, &mut ParticleEmitter)>,
+    time: Res<Time>,
+) {
+    for (entity, transform, mut emitter) in emitters.iter_mut() {
+        let origin = transform.translation;
+        if !emitter.started {
+            emitter.started = true;
+            for _ in 0..emitter.config.burst {
+                spawn_particle(&mut commands, &emitter.config, origin);
+            }
+        }
+        emitter.pending += emitter.config.rate * time.delta_secs();
+        while emitter.pending >= 1.0 {
+            emitter.pending -= 1.0;
+            spawn_particle(&mut commands, &emitter.config, origin);
+        }
+        if emitter.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn update_particles(
+    mut commands: Commands,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut particle, mut transform, mut sprite) in particles.iter_mut() {
+        if particle.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        particle.velocity.y += particle.gravity * dt;
+        transform.translation += (particle.velocity * dt).extend(0.0);
+
+        let t = particle.lifetime.fraction();
+        sprite.color = particle.start_color.mix(&particle.end_color, t).into();
+        let size = particle.start_size + (particle.end_size - particle.start_size) * t;
+        sprite.custom_size = Some(Vec2::splat(size));
+    }
+}
+
+pub fn update_floating_text(
+    mut commands: Commands,
+    mut texts: Query<(Entity, &mut FloatingText, &mut Transform, &mut TextColor)>,
+    time: Res<Time>,
+) {
+    for (entity, mut floating, mut transform, mut color) in texts.iter_mut() {
+        if floating.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.translation += (floating.velocity * time.delta_secs()).extend(0.0);
+        color.0.set_alpha(floating.lifetime.fraction_remaining());
+    }
+}
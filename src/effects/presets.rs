@@ -0,0 +1,134 @@
+use super::particles::*;
+use crate::engine::gameplay_events::GameplayEvent;
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+/// Effects are drawn in front of platforms, crates and the player.
+const EFFECTS_Z: f32 = 5.0;
+
+const POT_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+const BANKED_COLOR: Color = Color::srgb(0.3, 1.0, 0.4);
+const LOST_COLOR: Color = Color::srgb(1.0, 0.3, 0.2);
+
+pub fn crate_splinters() -> EmitterConfig {
+    EmitterConfig {
+        burst: 16,
+        rate: 0.0,
+        duration: 0.1,
+        particle_lifetime: 0.4..0.8,
+        speed: 120.0..260.0,
+        angle: 0.2..PI - 0.2,
+        gravity: -900.0,
+        start_color: LinearRgba::rgb(0.55, 0.35, 0.15),
+        end_color: LinearRgba::new(0.35, 0.2, 0.1, 0.0),
+        start_size: 6.0,
+        end_size: 3.0,
+    }
+}
+
+pub fn explosion_fireball() -> EmitterConfig {
+    EmitterConfig {
+        burst: 40,
+        rate: 0.0,
+        duration: 0.1,
+        particle_lifetime: 0.25..0.5,
+        speed: 80.0..320.0,
+        angle: 0.0..2.0 * PI,
+        gravity: 0.0,
+        start_color: LinearRgba::rgb(1.0, 0.9, 0.3),
+        end_color: LinearRgba::new(0.9, 0.2, 0.0, 0.0),
+        start_size: 18.0,
+        end_size: 6.0,
+    }
+}
+
+pub fn explosion_smoke() -> EmitterConfig {
+    EmitterConfig {
+        burst: 6,
+        rate: 30.0,
+        duration: 0.6,
+        particle_lifetime: 0.8..1.4,
+        speed: 20.0..60.0,
+        angle: 0.25 * PI..0.75 * PI,
+        gravity: 40.0,
+        start_color: LinearRgba::new(0.3, 0.3, 0.3, 0.8),
+        end_color: LinearRgba::new(0.5, 0.5, 0.5, 0.0),
+        start_size: 14.0,
+        end_size: 40.0,
+    }
+}
+
+pub fn landing_dust() -> EmitterConfig {
+    EmitterConfig {
+        burst: 10,
+        rate: 0.0,
+        duration: 0.1,
+        particle_lifetime: 0.3..0.5,
+        speed: 30.0..90.0,
+        angle: 0.0..PI,
+        gravity: -60.0,
+        start_color: LinearRgba::new(0.8, 0.75, 0.65, 0.7),
+        end_color: LinearRgba::new(0.8, 0.75, 0.65, 0.0),
+        start_size: 5.0,
+        end_size: 10.0,
+    }
+}
+
+fn spawn_emitter(commands: &mut Commands, config: EmitterConfig, position: Vec2) {
+    commands.spawn((
+        Transform::from_translation(position.extend(EFFECTS_Z)),
+        ParticleEmitter::new(config),
+    ));
+}
+
+fn spawn_score_popup(commands: &mut Commands, text: String, color: Color, position: Vec2) {
+    commands.spawn((
+        Text2d::new(text),
+        TextFont {
+            font_size: 28.0,
+            ..default()
+        },
+        TextColor(color),
+        Transform::from_translation((position + Vec2::Y * 30.0).extend(EFFECTS_Z)),
+        FloatingText {
+            velocity: Vec2::new(0.0, 60.0),
+            lifetime: Timer::from_seconds(1.0, TimerMode::Once),
+        },
+    ));
+}
+
+pub fn spawn_gameplay_effects(
+    mut commands: Commands,
+    mut gameplay_events: EventReader<GameplayEvent>,
+) {
+    for event in gameplay_events.read() {
+        match event {
+            GameplayEvent::CrateStruck {
+                position, points, ..
+            } => {
+                spawn_emitter(&mut commands, crate_splinters(), *position);
+                spawn_score_popup(&mut commands, format!("+{points}"), POT_COLOR, *position);
+            }
+            GameplayEvent::CrateExploded { position, .. } => {
+                spawn_emitter(&mut commands, explosion_fireball(), *position);
+                spawn_emitter(&mut commands, explosion_smoke(), *position);
+            }
+            GameplayEvent::PotBanked {
+                amount, position, ..
+            } => {
+                let text = format!("Banked {amount}!");
+                spawn_score_popup(&mut commands, text, BANKED_COLOR, *position);
+            }
+            GameplayEvent::PotLost {
+                amount, position, ..
+            } => {
+                let text = format!("Lost {amount}");
+                spawn_score_popup(&mut commands, text, LOST_COLOR, *position);
+            }
+            GameplayEvent::Landed { position, .. } => {
+                spawn_emitter(&mut commands, landing_dust(), *position);
+            }
+            _ => {}
+        }
+    }
+}
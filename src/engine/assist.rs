@@ -0,0 +1,206 @@
+use super::combat_feel::HitStop;
+use super::crate_strike::{in_strike_reach, strike_crates};
+use super::player::Player;
+use super::player_input::{PlayerInputs, ScriptedInput};
+use super::player_physics::Physics;
+use crate::animations::player_animation_state::*;
+use crate::environment::crate_spawner::WackyCrate;
+use crate::InGameState;
+use bevy::prelude::*;
+
+/// Game speeds offered in Settings, as a fraction of normal speed.
+pub const GAME_SPEEDS: [f32; 4] = [1.0, 0.85, 0.7, 0.5];
+pub const UI_SCALES: [f32; 3] = [1.0, 1.25, 1.5];
+pub const EXTRA_HP_STEPS: [u32; 4] = [0, 50, 100, 200];
+/// Seconds between automatic attacks while a crate stays in reach.
+pub const AUTO_ATTACK_INTERVAL: f32 = 0.6;
+
+/// The value after `current` in `steps`, wrapping around to the first.
+pub fn next_step<T: PartialEq + Copy>(steps: &[T], current: T) -> T {
+    let index = steps.iter().position(|step| *step == current).unwrap_or(0);
+    steps[(index + 1) % steps.len()]
+}
+
+/// Options that make the game easier to play for young or motor-impaired players.
+#[derive(Resource, Debug, Clone)]
+pub struct AssistSettings {
+    /// Relative speed of the whole game, 1.0 being normal.
+    pub game_speed: f32,
+    /// Shift switches running on and off instead of having to be held.
+    pub toggle_run: bool,
+    /// Attack on its own whenever a crate is in reach.
+    pub auto_attack: bool,
+    /// Softer damage flashes, no invulnerability blinking and no hit-stop freeze.
+    pub reduced_motion: bool,
+    pub ui_scale: f32,
+    /// Added to the player's hp at the start of each round.
+    pub extra_hp: u32,
+}
+
+impl Default for AssistSettings {
+    fn default() -> Self {
+        Self {
+            game_speed: 1.0,
+            toggle_run: false,
+            auto_attack: false,
+            reduced_motion: false,
+            ui_scale: 1.0,
+            extra_hp: 0,
+        }
+    }
+}
+
+/// Extra hp to use for the next round instead of the assist setting, e.g. when replaying
+/// a round recorded with different settings.
+#[derive(Resource, Default)]
+pub struct NextRoundExtraHp(pub Option<u32>);
+
+fn auto_attack_enabled(assist: Res<AssistSettings>) -> bool {
+    assist.auto_attack
+}
+
+pub struct AssistPlugin;
+
+impl Plugin for AssistPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AssistSettings>()
+            .init_resource::<NextRoundExtraHp>()
+            .add_systems(
+                Update,
+                apply_game_speed.run_if(resource_changed::<AssistSettings>),
+            )
+            .add_systems(
+                FixedUpdate,
+                auto_attack
+                    .before(player_movement_state)
+                    .before(strike_crates)
+                    .run_if(
+                        in_state(InGameState::Running)
+                            .and(not(resource_exists::<ScriptedInput>))
+                            .and(auto_attack_enabled),
+                    ),
+            );
+    }
+}
+
+/// Applies the game speed unless a hit-stop currently holds the game still; the hit-stop
+/// restores it when it ends.
+pub fn apply_game_speed(
+    assist: Res<AssistSettings>,
+    hit_stop: Res<HitStop>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if hit_stop.remaining <= 0.0 {
+        virtual_time.set_relative_speed(assist.game_speed);
+    }
+}
+
+/// Sends an attack for players standing next to a crate. The attack goes through
+/// `PlayerInputs`, so it is recorded into replays like any other input.
+pub fn auto_attack(
+    mut cooldown: Local<Option<Timer>>,
+    time: Res<Time>,
+    players: Query<(&Transform, &Physics, &PlayerState), With<Player>>,
+    crates: Query<&Transform, With<WackyCrate>>,
+    mut input_events: EventWriter<PlayerInputs>,
+) {
+    let cooldown =
+        cooldown.get_or_insert_with(|| Timer::from_seconds(AUTO_ATTACK_INTERVAL, TimerMode::Once));
+    if !cooldown.tick(time.delta()).finished() {
+        return;
+    }
+    let ready = players.iter().any(|(player_xf, physics, state)| {
+        physics.on_ground
+            && matches!(
+                state.current_state(),
+                PlayerStateKind::Idle | PlayerStateKind::Walk(_) | PlayerStateKind::Run(_)
+            )
+            && crates
+                .iter()
+                .any(|crate_xf| in_strike_reach(player_xf, crate_xf))
+    });
+    if ready {
+        input_events.send(PlayerInputs::Attack);
+        cooldown.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::event::Events;
+    use std::time::Duration;
+
+    #[test]
+    fn next_step_wraps_around() {
+        assert_eq!(next_step(&GAME_SPEEDS, 1.0), 0.85);
+        assert_eq!(next_step(&GAME_SPEEDS, 0.5), 1.0);
+        assert_eq!(next_step(&EXTRA_HP_STEPS, 200), 0);
+        // A value edited by hand starts the cycle again
+        assert_eq!(next_step(&UI_SCALES, 3.0), 1.25);
+    }
+
+    #[test]
+    fn defaults_are_the_first_steps() {
+        let assist = AssistSettings::default();
+        assert_eq!(assist.game_speed, GAME_SPEEDS[0]);
+        assert_eq!(assist.ui_scale, UI_SCALES[0]);
+        assert_eq!(assist.extra_hp, EXTRA_HP_STEPS[0]);
+        assert!(!assist.toggle_run && !assist.auto_attack && !assist.reduced_motion);
+    }
+
+    #[test]
+    fn game_speed_waits_for_the_hit_stop() {
+        let mut app = App::new();
+        app.insert_resource(AssistSettings {
+            game_speed: 0.5,
+            ..default()
+        })
+        .insert_resource(HitStop { remaining: 0.05 })
+        .init_resource::<Time<Virtual>>()
+        .add_systems(Update, apply_game_speed);
+        app.update();
+        assert_eq!(
+            app.world().resource::<Time<Virtual>>().relative_speed(),
+            1.0
+        );
+
+        app.world_mut().resource_mut::<HitStop>().remaining = 0.0;
+        app.update();
+        assert_eq!(
+            app.world().resource::<Time<Virtual>>().relative_speed(),
+            0.5
+        );
+    }
+
+    /// Runs `auto_attack` once a full interval has passed, with a crate `offset` in front
+    /// of a grounded player, and returns how many attacks it sent.
+    fn auto_attacks(offset: f32) -> usize {
+        let mut app = App::new();
+        app.add_event::<PlayerInputs>()
+            .init_resource::<Time>()
+            .add_systems(Update, auto_attack);
+        app.world_mut().spawn((
+            Player,
+            Transform::default(),
+            Physics {
+                on_ground: true,
+                ..default()
+            },
+            PlayerState::default(),
+        ));
+        app.world_mut()
+            .spawn((WackyCrate, Transform::from_xyz(offset, 0.0, 0.0)));
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(AUTO_ATTACK_INTERVAL));
+        app.update();
+        app.world().resource::<Events<PlayerInputs>>().len()
+    }
+
+    #[test]
+    fn auto_attack_only_swings_at_crates_in_reach() {
+        assert_eq!(auto_attacks(40.0), 1);
+        assert_eq!(auto_attacks(400.0), 0);
+    }
+}
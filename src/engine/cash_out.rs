@@ -0,0 +1,99 @@
+use super::gameplay_events::*;
+use super::player::*;
+use crate::environment::platform_spawner::CashOutStation;
+use bevy::prelude::*;
+
+/// Banks a player's pot as soon as they stand in a cash-out station. Dead players can't
+/// cash out.
+pub fn bank_pot(
+    mut players: Query<(Entity, &Transform, &SpriteSize, &mut PlayerStatus), With<Player>>,
+    stations: Query<(&Transform, &CashOutStation)>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+) {
+    for (player, player_xf, sprite_size, mut status) in players.iter_mut() {
+        if status.pot == 0 || status.hp == 0 {
+            continue;
+        }
+        let half_player = sprite_size.frame_size / 4.0; // Same footprint as hazard contact
+        let at_station = stations.iter().any(|(station_xf, station)| {
+            let offset = (player_xf.translation - station_xf.translation)
+                .truncate()
+                .abs();
+            offset.cmple(half_player + station.size / 2.0).all()
+        });
+        if !at_station {
+            continue;
+        }
+        let amount = std::mem::take(&mut status.pot);
+        status.points += amount;
+        status.streak = 0;
+        gameplay_events.send(GameplayEvent::PotBanked {
+            player,
+            amount,
+            position: player_xf.translation.truncate(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::event::Events;
+
+    /// A player holding `pot` after `streak` hits, standing `offset` from a station.
+    fn bank_app(pot: u32, streak: u32, offset: f32) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_event::<GameplayEvent>()
+            .add_systems(Update, bank_pot);
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_xyz(offset, 0.0, 0.0),
+                SpriteSize::default(),
+                PlayerStatus {
+                    points: 10,
+                    pot,
+                    streak,
+                    ..default()
+                },
+            ))
+            .id();
+        app.world_mut().spawn((
+            Transform::default(),
+            CashOutStation {
+                size: Vec2::splat(80.0),
+            },
+        ));
+        app.update();
+        (app, player)
+    }
+
+    #[test]
+    fn standing_at_a_station_banks_the_pot() {
+        let (app, player) = bank_app(6, 3, 20.0);
+
+        let status = app.world().get::<PlayerStatus>(player).unwrap();
+        assert_eq!(status.points, 16);
+        assert_eq!(status.pot, 0);
+        assert_eq!(status.streak, 0);
+        assert_eq!(app.world().resource::<Events<GameplayEvent>>().len(), 1);
+    }
+
+    #[test]
+    fn away_from_a_station_the_pot_stays_at_risk() {
+        let (app, player) = bank_app(6, 3, 500.0);
+
+        let status = app.world().get::<PlayerStatus>(player).unwrap();
+        assert_eq!((status.points, status.pot, status.streak), (10, 6, 3));
+        assert!(app.world().resource::<Events<GameplayEvent>>().is_empty());
+    }
+
+    #[test]
+    fn an_empty_pot_is_not_banked() {
+        let (app, player) = bank_app(0, 0, 0.0);
+
+        assert_eq!(app.world().get::<PlayerStatus>(player).unwrap().points, 10);
+        assert!(app.world().resource::<Events<GameplayEvent>>().is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+use super::assist::AssistSettings;
+use super::crate_strike::strike_crates;
+use super::game_mode::SelectedMode;
+use super::gameplay_events::*;
+use super::player::*;
+use crate::animations::player_animation_clips::AnimationClips;
+use crate::animations::player_animation_state::*;
+use crate::data::tuning::Tuning;
+use crate::environment::platform_spawner::Hazard;
+use crate::InGameState;
+use bevy::color::Mix;
+use bevy::prelude::*;
+
+pub const FLASH_COLOR: LinearRgba = LinearRgba::rgb(1.0, 0.15, 0.15);
+/// Seconds between visibility toggles while invulnerable.
+pub const BLINK_INTERVAL: f32 = 0.1;
+/// How far a damage flash tints towards `FLASH_COLOR` with reduced motion on.
+pub const REDUCED_FLASH_STRENGTH: f32 = 0.35;
+
+pub struct CombatFeelPlugin;
+
+impl Plugin for CombatFeelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>()
+            .init_resource::<HitStop>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    hazard_contact,
+                    apply_damage.after(strike_crates),
+                    trigger_hit_stop,
+                    update_invulnerability,
+                    despawn_expired,
+                )
+                    .chain()
+                    .run_if(in_state(InGameState::Running)),
+            )
+            .add_systems(Update, (update_hit_stop, update_damage_flash));
+    }
+}
+
+/// Remaining real-time seconds of the current hit-stop.
+#[derive(Resource, Default)]
+pub struct HitStop {
+    pub remaining: f32,
+}
+
+/// Tints a sprite towards `FLASH_COLOR` and fades back to its own colour.
+#[derive(Component)]
+pub struct DamageFlash {
+    pub timer: Timer,
+    base_color: Option<Color>,
+}
+
+impl DamageFlash {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            base_color: None,
+        }
+    }
+}
+
+/// I-frames: damage is ignored and the sprite blinks until the timer runs out.
+#[derive(Component)]
+pub struct Invulnerable {
+    pub timer: Timer,
+    pub blink: Timer,
+}
+
+#[derive(Component)]
+pub struct DespawnTimer(pub Timer);
+
+/// Resolves damage requests against player hp, entering `Hurt` (or `Dead`) and granting
+/// i-frames that last at least as long as the hurt clip. Nothing hurts in modes without
+/// damage.
+pub fn apply_damage(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+    mut players: Query<(&mut PlayerStatus, &mut PlayerState), Without<Invulnerable>>,
+    clips: Res<AnimationClips>,
+    tuning: Res<Tuning>,
+    mode: Res<SelectedMode>,
+) {
+    if !mode.0.player_takes_damage() {
+        damage_events.clear();
+        return;
+    }
+    for event in damage_events.read() {
+        // Also skips repeat hits in the same frame, since `Invulnerable` isn't applied yet
+        let Ok((mut status, mut state)) = players.get_mut(event.target) else {
+            continue;
+        };
+        if status.hp == 0 {
+            continue;
+        }
+        status.hp = status.hp.saturating_sub(event.amount);
+        gameplay_events.send(GameplayEvent::PlayerDamaged {
+            player: event.target,
+            amount: event.amount,
+        });
+        if status.hp == 0 {
+            state.push_state(PlayerStateKind::Dead);
+            commands
+                .entity(event.target)
+                .insert(DamageFlash::new(tuning.flash_secs));
+            continue;
+        }
+        state.push_state(PlayerStateKind::Hurt);
+
+        let hurt = &clips.hurt;
+        let hurt_secs = (hurt.end - hurt.start + 1) as f32 * hurt.frame_duration;
+        commands.entity(event.target).insert((
+            DamageFlash::new(tuning.flash_secs),
+            Invulnerable {
+                timer: Timer::from_seconds(
+                    tuning.invulnerability_secs.max(hurt_secs),
+                    TimerMode::Once,
+                ),
+                blink: Timer::from_seconds(BLINK_INTERVAL, TimerMode::Repeating),
+            },
+        ));
+    }
+}
+
+/// Damages players overlapping a level hazard.
+pub fn hazard_contact(
+    players: Query<(Entity, &Transform, &SpriteSize), (With<Player>, Without<Invulnerable>)>,
+    hazards: Query<(&Transform, &Hazard)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (player, player_xf, sprite_size) in players.iter() {
+        let half_player = sprite_size.frame_size / 4.0; // The character fills about half its frame
+        for (hazard_xf, hazard) in hazards.iter() {
+            let offset = (player_xf.translation - hazard_xf.translation)
+                .truncate()
+                .abs();
+            if offset.cmple(half_player + hazard.size / 2.0).all() {
+                damage_events.send(DamageEvent {
+                    target: player,
+                    amount: hazard.damage,
+                });
+                break;
+            }
+        }
+    }
+}
+
+pub fn trigger_hit_stop(
+    mut gameplay_events: EventReader<GameplayEvent>,
+    mut hit_stop: ResMut<HitStop>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    tuning: Res<Tuning>,
+    assist: Res<AssistSettings>,
+) {
+    if assist.reduced_motion {
+        gameplay_events.clear();
+        return;
+    }
+    for event in gameplay_events.read() {
+        if let GameplayEvent::CrateStruck { .. } | GameplayEvent::CrateExploded { .. } = event {
+            hit_stop.remaining = tuning.hit_stop_secs;
+            virtual_time.set_relative_speed(0.0);
+        }
+    }
+}
+
+pub fn update_hit_stop(
+    mut hit_stop: ResMut<HitStop>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+    assist: Res<AssistSettings>,
+) {
+    if hit_stop.remaining <= 0.0 {
+        return;
+    }
+    hit_stop.remaining -= real_time.delta_secs();
+    if hit_stop.remaining <= 0.0 {
+        virtual_time.set_relative_speed(assist.game_speed);
+    }
+}
+
+pub fn update_damage_flash(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut DamageFlash, &mut Sprite)>,
+    time: Res<Time<Real>>,
+    assist: Res<AssistSettings>,
+) {
+    let strength = if assist.reduced_motion {
+        REDUCED_FLASH_STRENGTH
+    } else {
+        1.0
+    };
+    for (entity, mut flash, mut sprite) in query.iter_mut() {
+        let base_color = *flash.base_color.get_or_insert(sprite.color);
+        if flash.timer.tick(time.delta()).finished() {
+            sprite.color = base_color;
+            commands.entity(entity).remove::<DamageFlash>();
+            continue;
+        }
+        let base: LinearRgba = base_color.into();
+        let tint = (1.0 - flash.timer.fraction()) * strength;
+        sprite.color = base.mix(&FLASH_COLOR, tint).into();
+    }
+}
+
+pub fn update_invulnerability(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Invulnerable, &mut Visibility)>,
+    time: Res<Time>,
+    assist: Res<AssistSettings>,
+) {
+    for (entity, mut invulnerable, mut visibility) in query.iter_mut() {
+        if invulnerable.timer.tick(time.delta()).finished() {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<Invulnerable>();
+            continue;
+        }
+        if assist.reduced_motion {
+            *visibility = Visibility::Inherited;
+        } else if invulnerable.blink.tick(time.delta()).just_finished() {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+}
+
+pub fn despawn_expired(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut DespawnTimer)>,
+    time: Res<Time>,
+) {
+    for (entity, mut despawn_timer) in query.iter_mut() {
+        if despawn_timer.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
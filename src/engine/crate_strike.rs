@@ -0,0 +1,242 @@
+use super::combat_feel::*;
+use super::gameplay_events::*;
+use super::player::*;
+use super::player_input::*;
+use super::player_physics::*;
+use super::simulation::GameRng;
+use crate::animations::player_animation_state::*;
+use crate::data::tuning::Tuning;
+use crate::environment::crate_spawner::*;
+use bevy::prelude::*;
+
+/// Horizontal distance in front of the player within which a crate can be struck.
+pub const STRIKE_REACH: f32 = 80.0;
+pub const STRIKE_HEIGHT: f32 = 80.0;
+
+/// Whether a crate at `crate_xf` is in front of the player and close enough to strike.
+pub fn in_strike_reach(player_xf: &Transform, crate_xf: &Transform) -> bool {
+    let facing = player_xf.scale.x.signum();
+    let offset = crate_xf.translation - player_xf.translation;
+    offset.x * facing >= -10.0 && offset.x.abs() <= STRIKE_REACH && offset.y.abs() <= STRIKE_HEIGHT
+}
+
+/// Debug override: while set, the next struck crate explodes whatever its roll.
+#[derive(Resource, Default)]
+pub struct ForceExplosion(pub bool);
+
+/// Resolves an attack against the nearest crate in front of the player. The crate rolls
+/// against its explosion chance: a safe hit adds to the player's at-risk pot, an explosion
+/// hurts the player and loses the pot.
+pub fn strike_crates(
+    mut commands: Commands,
+    mut input_events: EventReader<PlayerInputs>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut rng: ResMut<GameRng>,
+    mut force_explosion: ResMut<ForceExplosion>,
+    tuning: Res<Tuning>,
+    mut players: Query<
+        (
+            Entity,
+            &Transform,
+            &Physics,
+            &mut PlayerStatus,
+            &PlayerState,
+        ),
+        With<Player>,
+    >,
+    crates: Query<(Entity, &Transform, &CrateRisk), With<WackyCrate>>,
+) {
+    for event in input_events.read() {
+        let PlayerInputs::Attack = event else {
+            continue;
+        };
+        for (player, player_xf, physics, mut status, state) in players.iter_mut() {
+            let _span = debug_span!("strike", ?player).entered();
+            if !physics.on_ground
+                || matches!(
+                    state.current_state(),
+                    PlayerStateKind::Hurt | PlayerStateKind::Dead
+                )
+            {
+                continue;
+            }
+            let target = crates
+                .iter()
+                .filter(|(_, crate_xf, _)| in_strike_reach(player_xf, crate_xf))
+                .min_by(|(_, a, _), (_, b, _)| {
+                    let da = a.translation.distance_squared(player_xf.translation);
+                    let db = b.translation.distance_squared(player_xf.translation);
+                    da.total_cmp(&db)
+                });
+            let Some((crate_entity, crate_xf, risk)) = target else {
+                continue;
+            };
+            let position = crate_xf.translation.truncate();
+
+            // Always roll so forcing an outcome doesn't shift the rest of the random stream
+            let exploded = rng.chance(risk.explosion_chance);
+            // Take the override before the `||`, or a real explosion would leave it set
+            let forced = std::mem::take(&mut force_explosion.0);
+            if exploded || forced {
+                info!(
+                    chance = risk.explosion_chance,
+                    index = risk.index,
+                    "crate exploded"
+                );
+                commands.entity(crate_entity).despawn_recursive();
+                gameplay_events.send(GameplayEvent::CrateExploded {
+                    crate_entity,
+                    crate_index: risk.index,
+                    position,
+                    explosion_chance: risk.explosion_chance,
+                });
+                damage_events.send(DamageEvent {
+                    target: player,
+                    amount: tuning.explosion_damage,
+                });
+                let lost = std::mem::take(&mut status.pot);
+                status.streak = 0;
+                if lost > 0 {
+                    gameplay_events.send(GameplayEvent::PotLost {
+                        player,
+                        amount: lost,
+                        position: player_xf.translation.truncate(),
+                    });
+                }
+            } else {
+                // Let the crate flash before it breaks apart
+                commands.entity(crate_entity).remove::<CrateRisk>().insert((
+                    DamageFlash::new(tuning.flash_secs),
+                    DespawnTimer(Timer::from_seconds(tuning.flash_secs, TimerMode::Once)),
+                ));
+                // Consecutive hits are worth more, so the pot grows faster the longer it's at risk
+                status.streak += 1;
+                let points = status.streak;
+                status.pot += points;
+                debug!(
+                    chance = risk.explosion_chance,
+                    index = risk.index,
+                    "crate struck"
+                );
+                gameplay_events.send(GameplayEvent::CrateStruck {
+                    crate_entity,
+                    crate_index: risk.index,
+                    position,
+                    explosion_chance: risk.explosion_chance,
+                    points,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::event::Events;
+
+    /// A grounded player facing a crate within reach, holding `pot` after `streak` hits.
+    fn strike_app(explosion_chance: f32, pot: u32, streak: u32) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_event::<PlayerInputs>()
+            .add_event::<GameplayEvent>()
+            .add_event::<DamageEvent>()
+            .insert_resource(GameRng::new(7))
+            .init_resource::<ForceExplosion>()
+            .init_resource::<Tuning>()
+            .add_systems(Update, strike_crates);
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::default(),
+                Physics {
+                    on_ground: true,
+                    ..default()
+                },
+                PlayerStatus {
+                    pot,
+                    streak,
+                    ..default()
+                },
+                PlayerState::default(),
+            ))
+            .id();
+        app.world_mut().spawn((
+            WackyCrate,
+            Transform::from_xyz(40.0, 0.0, 0.0),
+            CrateRisk {
+                index: 3,
+                curve_step: 3,
+                explosion_chance,
+            },
+        ));
+        app.world_mut().send_event(PlayerInputs::Attack);
+        app.update();
+        (app, player)
+    }
+
+    fn gameplay_events(app: &App) -> Vec<GameplayEvent> {
+        let events = app.world().resource::<Events<GameplayEvent>>();
+        events.get_cursor().read(events).cloned().collect()
+    }
+
+    #[test]
+    fn safe_hit_grows_the_pot_by_the_streak() {
+        let (app, player) = strike_app(0.0, 5, 2);
+
+        let status = app.world().get::<PlayerStatus>(player).unwrap();
+        assert_eq!(status.streak, 3);
+        assert_eq!(status.pot, 8);
+        assert_eq!(status.points, 0);
+        assert!(matches!(
+            gameplay_events(&app)[..],
+            [GameplayEvent::CrateStruck {
+                crate_index: 3,
+                points: 3,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn explosion_loses_the_pot_and_resets_the_streak() {
+        let (app, player) = strike_app(1.0, 5, 2);
+
+        let status = app.world().get::<PlayerStatus>(player).unwrap();
+        assert_eq!(status.streak, 0);
+        assert_eq!(status.pot, 0);
+        assert_eq!(status.points, 0);
+        assert!(matches!(
+            gameplay_events(&app)[..],
+            [
+                GameplayEvent::CrateExploded { crate_index: 3, .. },
+                GameplayEvent::PotLost { amount: 5, .. }
+            ]
+        ));
+        assert_eq!(app.world().resource::<Events<DamageEvent>>().len(), 1);
+    }
+
+    #[test]
+    fn forced_explosion_applies_to_one_strike() {
+        let (mut app, player) = strike_app(0.0, 0, 0);
+        app.insert_resource(ForceExplosion(true));
+        app.world_mut().spawn((
+            WackyCrate,
+            Transform::from_xyz(40.0, 0.0, 0.0),
+            CrateRisk {
+                index: 4,
+                curve_step: 4,
+                explosion_chance: 0.0,
+            },
+        ));
+        app.world_mut().send_event(PlayerInputs::Attack);
+        app.update();
+
+        assert!(!app.world().resource::<ForceExplosion>().0);
+        let status = app.world().get::<PlayerStatus>(player).unwrap();
+        assert_eq!(status.streak, 0);
+        assert_eq!(status.pot, 0);
+    }
+}
@@ -0,0 +1,45 @@
+use super::crate_strike::ForceExplosion;
+use super::difficulty::DifficultyCurve;
+use crate::animations::asset_loader::ImageAssets;
+use crate::data::tuning::Tuning;
+use crate::environment::crate_spawner::*;
+use crate::environment::platform_spawner::PlatformLayer;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A change the debug inspector makes to a running round. Applied on a fixed step like
+/// player inputs, so replays record it and play it back on the same tick.
+#[derive(Event, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum DebugAction {
+    SpawnCrate {
+        position: Vec2,
+        layer: PlatformLayer,
+    },
+    /// Sets [`ForceExplosion`].
+    ForceExplosion(bool),
+}
+
+pub fn apply_debug_actions(
+    mut commands: Commands,
+    mut actions: EventReader<DebugAction>,
+    image_assets: Res<ImageAssets>,
+    tuning: Res<Tuning>,
+    mut crates_spawned: ResMut<CratesSpawned>,
+    difficulty: Res<DifficultyCurve>,
+    mut force_explosion: ResMut<ForceExplosion>,
+) {
+    for action in actions.read() {
+        match action {
+            DebugAction::SpawnCrate { position, layer } => spawn_wacky_crate(
+                &mut commands,
+                &image_assets,
+                &tuning,
+                &mut crates_spawned,
+                &difficulty,
+                *position,
+                layer.clone(),
+            ),
+            DebugAction::ForceExplosion(forced) => force_explosion.0 = *forced,
+        }
+    }
+}
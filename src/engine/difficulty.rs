@@ -0,0 +1,120 @@
+use super::game_mode::SelectedMode;
+use super::player::{Player, PlayerStatus};
+use super::time_attack::RoundClock;
+use crate::data::tuning::{SurvivalRules, Tuning};
+use crate::environment::crate_spawner::CratesSpawned;
+use bevy::prelude::*;
+
+/// What moves a round along the difficulty curve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CurvePace {
+    /// Every new crate is one step further than the last.
+    #[default]
+    PerCrate,
+    /// One step every `TimeAttackRules::step_secs`.
+    Timed,
+    /// One step every `SurvivalRules::points_per_step` banked points, which also speeds up
+    /// spawning.
+    Score,
+}
+
+/// How hard the round is right now. The crate spawner reads it for the spawn rate and
+/// the explosion model for each new crate's step on the explosion curve.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct DifficultyCurve {
+    pub pace: CurvePace,
+    /// Steps taken along the curve so far this round.
+    pub step: u32,
+}
+
+impl DifficultyCurve {
+    /// Step on the explosion curve for the crate spawned `crate_index`-th this round.
+    pub fn step_for_crate(&self, crate_index: u32) -> u32 {
+        match self.pace {
+            CurvePace::PerCrate => crate_index,
+            CurvePace::Timed | CurvePace::Score => self.step,
+        }
+    }
+
+    /// How many times faster than the round's base interval crates spawn.
+    pub fn spawn_rate(&self, rules: &SurvivalRules) -> f32 {
+        match self.pace {
+            CurvePace::Score => {
+                (1.0 + rules.spawn_rate_step * self.step as f32).clamp(1.0, rules.max_spawn_rate)
+            }
+            CurvePace::PerCrate | CurvePace::Timed => 1.0,
+        }
+    }
+}
+
+pub fn reset_difficulty(mut difficulty: ResMut<DifficultyCurve>, mode: Res<SelectedMode>) {
+    *difficulty = DifficultyCurve {
+        pace: mode.0.curve_pace(),
+        step: 0,
+    };
+}
+
+/// Moves the round along the curve by whatever its pace measures.
+pub fn advance_difficulty(
+    mut difficulty: ResMut<DifficultyCurve>,
+    tuning: Res<Tuning>,
+    crates_spawned: Res<CratesSpawned>,
+    clock: Option<Res<RoundClock>>,
+    players: Query<&PlayerStatus, With<Player>>,
+) {
+    let step = match difficulty.pace {
+        CurvePace::PerCrate => crates_spawned.0,
+        CurvePace::Timed => clock.map_or(0, |clock| clock.curve_step(&tuning.time_attack)),
+        CurvePace::Score => players.iter().next().map_or(0, |status| {
+            status.points / tuning.survival.points_per_step.max(1)
+        }),
+    };
+    if difficulty.step != step {
+        difficulty.step = step;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(pace: CurvePace, step: u32) -> DifficultyCurve {
+        DifficultyCurve { pace, step }
+    }
+
+    #[test]
+    fn per_crate_pace_steps_with_each_crate() {
+        let curve = curve(CurvePace::PerCrate, 7);
+        assert_eq!(curve.step_for_crate(0), 0);
+        assert_eq!(curve.step_for_crate(12), 12);
+    }
+
+    #[test]
+    fn timed_and_score_paces_use_the_round_step() {
+        for pace in [CurvePace::Timed, CurvePace::Score] {
+            let curve = curve(pace, 4);
+            assert_eq!(curve.step_for_crate(0), 4);
+            assert_eq!(curve.step_for_crate(12), 4);
+        }
+    }
+
+    #[test]
+    fn only_score_pace_speeds_up_spawning() {
+        let rules = SurvivalRules::default();
+        assert_eq!(curve(CurvePace::PerCrate, 10).spawn_rate(&rules), 1.0);
+        assert_eq!(curve(CurvePace::Timed, 10).spawn_rate(&rules), 1.0);
+
+        assert_eq!(curve(CurvePace::Score, 0).spawn_rate(&rules), 1.0);
+        let rate = curve(CurvePace::Score, 2).spawn_rate(&rules);
+        assert!((rate - (1.0 + 2.0 * rules.spawn_rate_step)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn spawn_rate_is_capped() {
+        let rules = SurvivalRules::default();
+        assert_eq!(
+            curve(CurvePace::Score, 1000).spawn_rate(&rules),
+            rules.max_spawn_rate
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use super::combat_feel::apply_damage;
+use super::crate_strike::strike_crates;
+use super::difficulty::*;
+use super::gameplay_events::GameplayEvent;
+use super::player::{Player, PlayerStatus};
+use super::player_input::PlayerInputs;
+use super::round_end::end_round;
+use super::time_attack::*;
+use crate::environment::crate_spawner::spawn_crate;
+use crate::{GameState, InGameState};
+use bevy::ecs::event::EventCursor;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Points banked for every crate whose outcome the player called correctly.
+pub const PREDICTION_BONUS: u32 = 2;
+
+/// Rule set a round is played with, picked on the mode select screen.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    /// Before striking, the player calls whether the crate will explode.
+    Prediction,
+    /// Score as much as possible before the clock runs out.
+    TimeAttack,
+    /// Crates keep coming, faster and riskier as the score grows, until the player's hp
+    /// runs out.
+    Survival,
+    /// Explosions still empty the pot but never hurt, for trying things out.
+    FreePlay,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 5] = [
+        GameMode::Classic,
+        GameMode::Prediction,
+        GameMode::TimeAttack,
+        GameMode::Survival,
+        GameMode::FreePlay,
+    ];
+
+    /// String table key of the mode name.
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "mode.classic",
+            GameMode::Prediction => "mode.prediction",
+            GameMode::TimeAttack => "mode.time_attack",
+            GameMode::Survival => "mode.survival",
+            GameMode::FreePlay => "mode.free_play",
+        }
+    }
+
+    /// String table key of the one-line explanation shown on the mode select screen.
+    pub fn description_key(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "mode.classic.about",
+            GameMode::Prediction => "mode.prediction.about",
+            GameMode::TimeAttack => "mode.time_attack.about",
+            GameMode::Survival => "mode.survival.about",
+            GameMode::FreePlay => "mode.free_play.about",
+        }
+    }
+
+    pub fn player_takes_damage(&self) -> bool {
+        *self != GameMode::FreePlay
+    }
+
+    /// Whether the round is over once the player has no hp left, rather than leaving
+    /// them down until they quit.
+    pub fn ends_when_out_of_hp(&self) -> bool {
+        matches!(self, GameMode::TimeAttack | GameMode::Survival)
+    }
+
+    pub fn curve_pace(&self) -> CurvePace {
+        match self {
+            GameMode::TimeAttack => CurvePace::Timed,
+            GameMode::Survival => CurvePace::Score,
+            GameMode::Classic | GameMode::Prediction | GameMode::FreePlay => CurvePace::PerCrate,
+        }
+    }
+}
+
+/// Mode the next round is played in.
+#[derive(Resource, Default)]
+pub struct SelectedMode(pub GameMode);
+
+/// The player's calls in Prediction mode: whether the next crate they strike explodes.
+#[derive(Component, Default, Debug)]
+pub struct PredictionCalls {
+    /// `Some(true)` if the player called an explosion, `None` until they make a call.
+    pub pending: Option<bool>,
+    pub correct: u32,
+    pub made: u32,
+}
+
+fn prediction_mode(mode: Res<SelectedMode>) -> bool {
+    mode.0 == GameMode::Prediction
+}
+
+pub struct GameModePlugin;
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedMode>()
+            .init_resource::<DifficultyCurve>()
+            .add_systems(
+                OnEnter(GameState::InGame),
+                (start_round_clock, reset_difficulty),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    record_predictions.before(strike_crates),
+                    score_predictions.after(strike_crates),
+                )
+                    .run_if(in_state(InGameState::Running).and(prediction_mode)),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    tick_round_clock.run_if(resource_exists::<RoundClock>),
+                    advance_difficulty.before(spawn_crate),
+                    end_round.after(apply_damage),
+                )
+                    .chain()
+                    .run_if(in_state(InGameState::Running)),
+            );
+    }
+}
+
+/// Keeps the player's latest call until a crate is struck. Calls are `PlayerInputs`, so
+/// replays reproduce them.
+pub fn record_predictions(
+    mut input_events: EventReader<PlayerInputs>,
+    mut players: Query<&mut PredictionCalls, With<Player>>,
+) {
+    for event in input_events.read() {
+        let call = match event {
+            PlayerInputs::PredictExplosion => true,
+            PlayerInputs::PredictSafe => false,
+            _ => continue,
+        };
+        for mut calls in players.iter_mut() {
+            calls.pending = Some(call);
+        }
+    }
+}
+
+/// Settles the pending call once a crate is struck, banking `PREDICTION_BONUS` for a
+/// right call.
+pub fn score_predictions(
+    mut gameplay_events: ResMut<Events<GameplayEvent>>,
+    mut cursor: Local<EventCursor<GameplayEvent>>,
+    mut players: Query<(Entity, &mut PredictionCalls, &mut PlayerStatus), With<Player>>,
+) {
+    // Collected first, as settling a call sends a gameplay event of its own
+    let outcomes: Vec<_> = cursor
+        .read(&gameplay_events)
+        .filter_map(|event| match event {
+            GameplayEvent::CrateStruck {
+                crate_index,
+                explosion_chance,
+                ..
+            } => Some((false, *crate_index, *explosion_chance)),
+            GameplayEvent::CrateExploded {
+                crate_index,
+                explosion_chance,
+                ..
+            } => Some((true, *crate_index, *explosion_chance)),
+            _ => None,
+        })
+        .collect();
+    for (exploded, crate_index, explosion_chance) in outcomes {
+        for (player, mut calls, mut status) in players.iter_mut() {
+            let Some(call) = calls.pending.take() else {
+                continue;
+            };
+            calls.made += 1;
+            let correct = call == exploded;
+            if correct {
+                calls.correct += 1;
+                status.points += PREDICTION_BONUS;
+            }
+            gameplay_events.send(GameplayEvent::PredictionSettled {
+                player,
+                called_explosion: call,
+                correct,
+                crate_index,
+                explosion_chance,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn struck_crate_settles_the_pending_call() {
+        let mut app = App::new();
+        app.add_event::<GameplayEvent>()
+            .add_systems(Update, score_predictions);
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                PlayerStatus::default(),
+                PredictionCalls {
+                    pending: Some(false),
+                    ..default()
+                },
+            ))
+            .id();
+        app.world_mut().send_event(GameplayEvent::CrateStruck {
+            crate_entity: Entity::PLACEHOLDER,
+            crate_index: 5,
+            position: Vec2::ZERO,
+            explosion_chance: 0.3,
+            points: 1,
+        });
+        app.update();
+
+        let calls = app.world().get::<PredictionCalls>(player).unwrap();
+        assert_eq!((calls.pending, calls.made, calls.correct), (None, 1, 1));
+        let status = app.world().get::<PlayerStatus>(player).unwrap();
+        assert_eq!(status.points, PREDICTION_BONUS);
+        let events = app.world().resource::<Events<GameplayEvent>>();
+        let settled: Vec<_> = events
+            .get_cursor()
+            .read(events)
+            .filter(|event| matches!(event, GameplayEvent::PredictionSettled { .. }))
+            .cloned()
+            .collect();
+        assert!(matches!(
+            settled[..],
+            [GameplayEvent::PredictionSettled {
+                called_explosion: false,
+                correct: true,
+                crate_index: 5,
+                ..
+            }]
+        ));
+    }
+}
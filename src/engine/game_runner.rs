@@ -1,27 +1,45 @@
+use super::assist::AssistPlugin;
+use super::combat_feel::CombatFeelPlugin;
+use super::game_mode::GameModePlugin;
 use super::player::AddPlayerPlugin;
 use super::player_physics::*;
-use crate::animations::asset_loader::*;
+use super::simulation::SimulationPlugin;
+use crate::add_game_states;
+use crate::data::data_plugin::DataPlugin;
+use crate::data::tuning::Tuning;
 use crate::environment::environment_plugin::SpawnEnvironmentsPlugin;
-use crate::GameState;
 use avian2d::prelude::*;
 use bevy::prelude::*;
-use bevy_asset_loader::prelude::*;
 
+/// All gameplay: player, combat, physics and environment. Sets up the game states and
+/// loads the gameplay tuning itself, so it also works without `WackyCratesPlugin`.
+///
+/// Gameplay runs in `FixedUpdate`, and avian steps in `FixedPostUpdate` right after it, so
+/// the hand-rolled player physics and the crate rigid bodies share one clock.
 pub struct GameRunnerPlugin;
 
 impl Plugin for GameRunnerPlugin {
     fn build(&self, app: &mut App) {
+        add_game_states(app);
         app.add_plugins((
+            DataPlugin,
             AddPlayerPlugin,
+            AssistPlugin,
+            CombatFeelPlugin,
+            GameModePlugin,
             PlayerPhysicsPlugin,
+            SimulationPlugin,
             SpawnEnvironmentsPlugin,
             PhysicsPlugins::default().with_length_unit(0.00001),
         ))
-        .add_loading_state(
-            LoadingState::new(GameState::AssetLoading)
-                .load_collection::<ImageAssets>()
-                .continue_to_state(GameState::InGame),
-        )
-        .insert_resource(Gravity(bevy::prelude::Vec2::new(0.0, -327.0)));
+        .insert_resource(Gravity(bevy::prelude::Vec2::new(0.0, -327.0)))
+        .add_systems(
+            Update,
+            apply_world_gravity.run_if(resource_changed::<Tuning>),
+        );
     }
 }
+
+fn apply_world_gravity(tuning: Res<Tuning>, mut gravity: ResMut<Gravity>) {
+    gravity.0 = bevy::prelude::Vec2::new(0.0, tuning.world_gravity);
+}
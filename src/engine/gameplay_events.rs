@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+/// Things that happen during a round that other subsystems (audio, effects, telemetry)
+/// react to. Gameplay systems only send these; they never call into those subsystems.
+#[derive(Event, Debug, Clone)]
+pub enum GameplayEvent {
+    Footstep {
+        player: Entity,
+    },
+    Jumped {
+        player: Entity,
+    },
+    Landed {
+        player: Entity,
+        position: Vec2,
+    },
+    Attacked {
+        player: Entity,
+    },
+    CrateStruck {
+        crate_entity: Entity,
+        /// Position of the crate in the round's spawn order.
+        crate_index: u32,
+        position: Vec2,
+        explosion_chance: f32,
+        points: u32,
+    },
+    CrateExploded {
+        crate_entity: Entity,
+        crate_index: u32,
+        position: Vec2,
+        explosion_chance: f32,
+    },
+    PlayerDamaged {
+        player: Entity,
+        amount: u32,
+    },
+    /// The player reached a cash-out station and moved their at-risk pot into their score.
+    PotBanked {
+        player: Entity,
+        amount: u32,
+        position: Vec2,
+    },
+    /// An explosion wiped out the player's unbanked pot.
+    PotLost {
+        player: Entity,
+        amount: u32,
+        position: Vec2,
+    },
+    /// A Prediction mode call was settled by the crate the player struck.
+    PredictionSettled {
+        player: Entity,
+        called_explosion: bool,
+        correct: bool,
+        crate_index: u32,
+        explosion_chance: f32,
+    },
+}
+
+/// Request to hurt an entity. Resolved by `apply_damage`, which honours invulnerability.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: u32,
+}
@@ -1,4 +1,15 @@
+pub mod assist;
+pub mod cash_out;
+pub mod combat_feel;
+pub mod crate_strike;
+pub mod debug_action;
+pub mod difficulty;
+pub mod game_mode;
 pub mod game_runner;
+pub mod gameplay_events;
 pub mod player;
 pub mod player_input;
 pub mod player_physics;
+pub mod round_end;
+pub mod simulation;
+pub mod time_attack;
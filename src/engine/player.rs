@@ -3,14 +3,28 @@ use crate::animations::player_animation_clips::*;
 use crate::animations::player_animation_state::*;
 use crate::animations::player_animations::*;
 use crate::animations::sprite_animation::*;
+use crate::data::ron_asset::RonAssetAppExt;
+use crate::data::tuning::Tuning;
+use crate::engine::assist::*;
+use crate::engine::cash_out::*;
+use crate::engine::crate_strike::*;
+use crate::engine::debug_action::*;
+use crate::engine::game_mode::*;
+use crate::engine::gameplay_events::*;
 use crate::engine::player_input::*;
 use crate::engine::player_physics::*;
-use crate::GameState;
+use crate::{GameState, InGameState};
 use avian2d::prelude::Collider;
 use avian2d::prelude::RigidBody;
+use avian2d::prelude::TranslationInterpolation;
 use bevy::prelude::*;
+/// Spawns the player on entering `GameState::InGame` and runs input, movement and animation.
+///
+/// Keyboard input is read once per frame, right before the fixed main loop; everything
+/// that moves the player or decides the outcome of a round runs in `FixedUpdate`.
 pub struct AddPlayerPlugin;
 
+/// Marks the controllable character.
 #[derive(Component)]
 #[require(Sprite, PlayerState, SpriteAnimState, Physics, SpriteSize)]
 pub struct Player;
@@ -28,10 +42,19 @@ impl Default for SpriteSize {
     }
 }
 
+/// Health, score and combo progress of a player.
+///
+/// `points` is the banked score. Safe hits add to `pot` instead, which is only kept once
+/// the player reaches a cash-out station; an explosion empties it.
 #[derive(Component)]
 pub struct PlayerStatus {
     pub hp: u32,
+    /// Hp the round started with, including any extra hp from the assist settings.
+    pub max_hp: u32,
     pub points: u32,
+    pub pot: u32,
+    /// Safe hits since the pot was last banked or lost. Each one is worth one more point.
+    pub streak: u32,
     pub attack_combo: u8,
     pub idle_timer: Timer,
 }
@@ -40,7 +63,10 @@ impl Default for PlayerStatus {
     fn default() -> Self {
         Self {
             hp: 100,
+            max_hp: 100,
             points: 0,
+            pot: 0,
+            streak: 0,
             attack_combo: 0,
             idle_timer: Timer::from_seconds(2.0, TimerMode::Once),
         }
@@ -51,19 +77,34 @@ impl Plugin for AddPlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<PlayerInputs>()
             .add_event::<AnimationEvent>()
+            .add_event::<GameplayEvent>()
+            .add_event::<DebugAction>()
+            .init_resource::<ForceExplosion>()
+            .init_ron_asset::<AnimationClips>()
+            .init_resource::<AnimationClips>()
+            .add_systems(Startup, load_animation_clips)
+            .add_systems(Update, sync_animation_clips)
+            .add_systems(OnEnter(GameState::InGame), setup_player)
             .add_systems(
-                OnEnter(GameState::InGame),
-                (setup_animation_clips, setup_player).chain(),
+                RunFixedMainLoop,
+                keyboard_input
+                    .in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop)
+                    .run_if(
+                        in_state(InGameState::Running).and(not(resource_exists::<ScriptedInput>)),
+                    ),
             )
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
-                    keyboard_input,
                     player_movement_state,
                     (animate_sprite, update_player_animation, handle_attack_combo).chain(),
                     player_sprite_movement,
+                    player_footsteps,
+                    apply_debug_actions.before(strike_crates),
+                    strike_crates.after(player_movement_state),
+                    bank_pot.after(collision_system),
                 )
-                    .run_if(in_state(GameState::InGame)),
+                    .run_if(in_state(InGameState::Running)),
             )
             .add_systems(OnExit(GameState::InGame), despawn_player);
     }
@@ -73,20 +114,25 @@ fn setup_player(
     mut commands: Commands,
     image_assets: Res<ImageAssets>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    assist: Res<AssistSettings>,
+    mut next_extra_hp: ResMut<NextRoundExtraHp>,
+    mode: Res<SelectedMode>,
+    tuning: Res<Tuning>,
 ) {
+    let extra_hp = next_extra_hp.0.take().unwrap_or(assist.extra_hp);
     // Define frame sizes
     let frame_size = UVec2::new(128, 128);
 
     // Create TextureAtlasLayouts
     let player_layout_handle = texture_atlases.add(TextureAtlasLayout::from_grid(
         frame_size as UVec2,
-        75 as u32,
+        75,
         1,
         None,
         None,
     ));
 
-    commands.spawn((
+    let mut player = commands.spawn((
         Player,
         Sprite {
             image: image_assets.player.clone(),
@@ -100,11 +146,20 @@ fn setup_player(
         SpriteAnimState::default(),
         Physics::default(),
         SpriteSize::default(),
-        PlayerStatus::default(),
+        PlayerStatus {
+            hp: tuning.player_hp + extra_hp,
+            max_hp: tuning.player_hp + extra_hp,
+            ..default()
+        },
         RigidBody::Kinematic,
         Collider::ellipse(10.0, 20.0),
+        // Smooths the fixed-step movement between rendered frames
+        TranslationInterpolation,
         Transform::from_xyz(0.0, 0.0, 0.0),
     ));
+    if mode.0 == GameMode::Prediction {
+        player.insert(PredictionCalls::default());
+    }
 }
 
 fn despawn_player(mut commands: Commands, query: Query<Entity, With<SpriteAnimState>>) {
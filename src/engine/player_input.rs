@@ -1,6 +1,9 @@
+use super::assist::AssistSettings;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Event, Debug)]
+/// Player intents, sent by `keyboard_input` or directly by tests and replays.
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
 pub enum PlayerInputs {
     Walk(MoveDirection),
     Run,
@@ -8,26 +11,54 @@ pub enum PlayerInputs {
     Jump,
     WalkEnd(MoveDirection),
     RunEnd,
+    /// Prediction mode calls for the next struck crate.
+    PredictExplosion,
+    PredictSafe,
 }
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum MoveDirection {
     Left,
     Right,
 }
 
+/// While present, `keyboard_input` is skipped and `PlayerInputs` come from a script
+/// such as a replay.
+#[derive(Resource)]
+pub struct ScriptedInput;
+
+/// With toggle-run on, Shift switches running on and off and every new walk starts as a
+/// run while it is on. Running only applies while walking, so walks get a `Run` right
+/// after their `Walk`.
 pub fn keyboard_input(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    assist: Res<AssistSettings>,
+    mut run_toggled: Local<bool>,
     mut player_move_event: EventWriter<PlayerInputs>,
 ) {
+    let toggled_run = assist.toggle_run && *run_toggled;
     // handle key presses
     if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
         player_move_event.send(PlayerInputs::Walk(MoveDirection::Left));
+        if toggled_run {
+            player_move_event.send(PlayerInputs::Run);
+        }
     }
     if keyboard_input.just_pressed(KeyCode::ArrowRight) {
         player_move_event.send(PlayerInputs::Walk(MoveDirection::Right));
+        if toggled_run {
+            player_move_event.send(PlayerInputs::Run);
+        }
     }
     if keyboard_input.just_pressed(KeyCode::ShiftLeft) {
-        player_move_event.send(PlayerInputs::Run);
+        if !assist.toggle_run {
+            player_move_event.send(PlayerInputs::Run);
+        } else if *run_toggled {
+            *run_toggled = false;
+            player_move_event.send(PlayerInputs::RunEnd);
+        } else {
+            *run_toggled = true;
+            player_move_event.send(PlayerInputs::Run);
+        }
     }
     if keyboard_input.just_pressed(KeyCode::Enter) {
         player_move_event.send(PlayerInputs::Attack);
@@ -35,6 +66,12 @@ pub fn keyboard_input(
     if keyboard_input.just_pressed(KeyCode::Space) {
         player_move_event.send(PlayerInputs::Jump);
     }
+    if keyboard_input.just_pressed(KeyCode::KeyE) {
+        player_move_event.send(PlayerInputs::PredictExplosion);
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyS) {
+        player_move_event.send(PlayerInputs::PredictSafe);
+    }
 
     // handle key releases
 
@@ -44,7 +81,7 @@ pub fn keyboard_input(
     if keyboard_input.just_released(KeyCode::ArrowRight) {
         player_move_event.send(PlayerInputs::WalkEnd(MoveDirection::Right));
     }
-    if keyboard_input.just_released(KeyCode::ShiftLeft) {
+    if keyboard_input.just_released(KeyCode::ShiftLeft) && !assist.toggle_run {
         player_move_event.send(PlayerInputs::RunEnd);
     }
 }
@@ -1,6 +1,8 @@
+use crate::data::tuning::Tuning;
+use crate::engine::gameplay_events::GameplayEvent;
 use crate::engine::player::*;
 use crate::environment::platform_spawner::*;
-use crate::GameState;
+use crate::InGameState;
 
 use bevy::prelude::*;
 
@@ -26,18 +28,22 @@ pub struct PlayerPhysicsPlugin;
 impl Plugin for PlayerPhysicsPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
+            FixedUpdate,
             (gravity_system, collision_system)
                 .chain()
-                .run_if(in_state(GameState::InGame)),
+                .run_if(in_state(InGameState::Running)),
         );
     }
 }
 
-pub fn gravity_system(mut query: Query<(&mut Physics, &mut Transform)>, time: Res<Time>) {
+pub fn gravity_system(
+    mut query: Query<(&mut Physics, &mut Transform)>,
+    tuning: Res<Tuning>,
+    time: Res<Time>,
+) {
     for (mut physics, mut transform) in query.iter_mut() {
         if !physics.on_ground {
-            physics.acceleration.y = -981.0; // Apply gravity
+            physics.acceleration.y = tuning.player_gravity; // Apply gravity
         } else {
             physics.acceleration.y = 0.0; // No gravity when on the ground
         }
@@ -47,26 +53,43 @@ pub fn gravity_system(mut query: Query<(&mut Physics, &mut Transform)>, time: Re
 
         // Update the translation with the new velocity
         transform.translation += physics.velocity * time.delta_secs();
-        //println!("Physics {:?}", physics);
+        trace!(?physics, "gravity step");
     }
 }
 
 pub fn collision_system(
-    mut query: Query<(&mut Physics, &mut Transform, &SpriteSize), Without<Platform>>,
+    mut query: Query<(Entity, &mut Physics, &mut Transform, &SpriteSize), Without<Platform>>,
     ground_query: Query<&Platform>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
 ) {
-    let applicable_level = ground_query.iter().map(|p| p.level as isize).max().unwrap();
-
-    for (mut physics, mut transform, sprite_size) in query.iter_mut() {
-        let ground_level = applicable_level as f32 + 0.5 * sprite_size.frame_size.y; // Adjust ground level based on player size
+    for (entity, mut physics, mut transform, sprite_size) in query.iter_mut() {
+        // Adjust ground level based on player size
+        let half_height = 0.5 * sprite_size.frame_size.y;
+        // Stand on the highest platform whose top is below the player's centre
+        let applicable_level = ground_query
+            .iter()
+            .filter(|p| p.spans(transform.translation.x) && p.level <= transform.translation.y)
+            .map(|p| p.level)
+            .reduce(f32::max);
+        let Some(applicable_level) = applicable_level else {
+            physics.on_ground = false;
+            continue;
+        };
+        let ground_level = applicable_level + half_height;
 
         if transform.translation.y <= ground_level && physics.velocity.y <= 0.0 {
             transform.translation.y = ground_level;
             physics.velocity.y = 0.0;
+            if !physics.on_ground {
+                gameplay_events.send(GameplayEvent::Landed {
+                    player: entity,
+                    position: Vec2::new(transform.translation.x, applicable_level),
+                });
+            }
             physics.on_ground = true; // Set to true when on the ground
         } else {
             physics.on_ground = false; // Set to false when in the air
         }
-        //println!("Ground {:?}, Physics {:?}, transform {:?}", Platform, physics , transform);
+        trace!(?physics, ground = applicable_level, "ground check");
     }
 }
@@ -0,0 +1,65 @@
+use super::difficulty::DifficultyCurve;
+use super::game_mode::{GameMode, SelectedMode};
+use super::player::{Player, PlayerStatus};
+use super::simulation::GameRng;
+use super::time_attack::RoundClock;
+use crate::environment::level_asset::{LevelCatalog, SelectedLevel};
+use crate::GameState;
+use bevy::prelude::*;
+
+/// How the last round went, for the game over screen and the leaderboard.
+#[derive(Resource, Clone, Debug)]
+pub struct RoundResult {
+    pub mode: GameMode,
+    pub seed: u64,
+    /// Path of the level asset the round was played on.
+    pub level: String,
+    /// Banked points. Whatever was still in the pot is lost.
+    pub score: u32,
+    /// True if the clock ran out, false if the player ran out of hp.
+    pub out_of_time: bool,
+    /// How far along the difficulty curve the round got.
+    pub curve_step: u32,
+}
+
+/// Ends the round once the Time Attack clock runs out, or the player has no hp left in
+/// a mode that stops there.
+pub fn end_round(
+    mut commands: Commands,
+    mode: Res<SelectedMode>,
+    clock: Option<Res<RoundClock>>,
+    difficulty: Res<DifficultyCurve>,
+    players: Query<&PlayerStatus, With<Player>>,
+    rng: Res<GameRng>,
+    catalog: Res<LevelCatalog>,
+    selected: Res<SelectedLevel>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let Some(status) = players.iter().next() else {
+        return;
+    };
+    let out_of_time = clock.is_some_and(|clock| clock.0.finished());
+    let out_of_hp = status.hp == 0 && mode.0.ends_when_out_of_hp();
+    if !out_of_time && !out_of_hp {
+        return;
+    }
+    let level = catalog
+        .0
+        .get(selected.0)
+        .map_or_else(String::new, |entry| entry.path.to_string());
+    info!(
+        score = status.points,
+        out_of_time,
+        curve_step = difficulty.step,
+        "round over"
+    );
+    commands.insert_resource(RoundResult {
+        mode: mode.0,
+        seed: rng.seed(),
+        level,
+        score: status.points,
+        out_of_time,
+        curve_step: difficulty.step,
+    });
+    game_state.set(GameState::GameOver);
+}
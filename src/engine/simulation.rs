@@ -0,0 +1,85 @@
+use crate::InGameState;
+use bevy::prelude::*;
+use rand::distr::uniform::{SampleRange, SampleUniform};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// The one source of randomness for gameplay. Reseeded at the start of every round so a
+/// round can be reproduced from its seed and inputs.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn random_range<T: SampleUniform, R: SampleRange<T>>(&mut self, range: R) -> T {
+        self.rng.random_range(range)
+    }
+
+    /// Returns true with the given probability.
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.rng.random::<f32>() < probability
+    }
+}
+
+/// Rate of the fixed gameplay step. Movement, timers and physics all advance in whole
+/// steps of this size, so a round plays the same at any frame rate.
+pub const SIMULATION_HZ: f64 = 60.0;
+
+/// Seed to use for the next round instead of a fresh random one, e.g. when replaying.
+#[derive(Resource, Default)]
+pub struct NextRoundSeed(pub Option<u64>);
+
+/// Fixed timesteps since the round started. Recorded inputs are keyed by this, and since
+/// the fixed clock follows virtual time it also stops while paused and speeds up when
+/// seeking through a replay.
+#[derive(Resource, Default, Debug)]
+pub struct SimulationTick(pub u64);
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+            .init_resource::<GameRng>()
+            .init_resource::<NextRoundSeed>()
+            .init_resource::<SimulationTick>()
+            .add_systems(OnEnter(crate::GameState::InGame), seed_round)
+            .add_systems(
+                FixedFirst,
+                advance_tick.run_if(in_state(InGameState::Running)),
+            );
+    }
+}
+
+pub fn seed_round(
+    mut rng: ResMut<GameRng>,
+    mut next_seed: ResMut<NextRoundSeed>,
+    mut tick: ResMut<SimulationTick>,
+) {
+    *rng = GameRng::new(next_seed.0.take().unwrap_or_else(rand::random));
+    tick.0 = 0;
+    info!(seed = rng.seed(), "round started");
+}
+
+pub fn advance_tick(mut tick: ResMut<SimulationTick>) {
+    tick.0 += 1;
+}
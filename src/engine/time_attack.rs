@@ -0,0 +1,30 @@
+use super::game_mode::{GameMode, SelectedMode};
+use crate::data::tuning::{TimeAttackRules, Tuning};
+use bevy::prelude::*;
+
+/// Time played in a Time Attack round. Only present while one is being played.
+#[derive(Resource, Debug)]
+pub struct RoundClock(pub Timer);
+
+impl RoundClock {
+    /// Steps taken up the explosion curve so far, one for every `step_secs` played.
+    pub fn curve_step(&self, rules: &TimeAttackRules) -> u32 {
+        (self.0.elapsed_secs() / rules.step_secs.max(f32::EPSILON)) as u32
+    }
+}
+
+pub fn start_round_clock(mut commands: Commands, mode: Res<SelectedMode>, tuning: Res<Tuning>) {
+    if mode.0 == GameMode::TimeAttack {
+        commands.insert_resource(RoundClock(Timer::from_seconds(
+            tuning.time_attack.round_secs,
+            TimerMode::Once,
+        )));
+    } else {
+        commands.remove_resource::<RoundClock>();
+    }
+}
+
+/// Advances on the fixed clock, so pauses and replays keep the same time.
+pub fn tick_round_clock(mut clock: ResMut<RoundClock>, time: Res<Time>) {
+    clock.0.tick(time.delta());
+}
@@ -1,49 +1,129 @@
 use super::environment_plugin::*;
+use super::level_asset::*;
 use super::platform_spawner::*;
+use crate::accessibility::palette::LayerVisual;
 use crate::animations::asset_loader::ImageAssets;
+use crate::data::tuning::Tuning;
+use crate::engine::difficulty::DifficultyCurve;
 use crate::engine::player::*;
+use crate::engine::simulation::GameRng;
 use avian2d::prelude::*;
 use bevy::prelude::*;
-use bevy::sprite::ColorMaterialPlugin;
-use bevy::window::PrimaryWindow;
-use bevy_egui::egui::ColorImage;
+use std::time::Duration;
 
+/// A crate that can be struck for points, or explode.
 #[derive(Component)]
 #[require(Sprite, SpriteSize)]
 pub struct WackyCrate;
 
+/// How likely a crate is to explode when struck. `index` is the crate's position in the
+/// round's spawn order.
+#[derive(Component, Debug)]
+pub struct CrateRisk {
+    pub index: u32,
+    /// Position on the explosion curve: the same as `index`, unless the mode moves along
+    /// the curve by time or score.
+    pub curve_step: u32,
+    pub explosion_chance: f32,
+}
+
+/// Number of crates spawned so far this round.
+#[derive(Resource, Default)]
+pub struct CratesSpawned(pub u32);
+
 pub fn spawn_crate(
     mut commands: Commands,
     image_assets: Res<ImageAssets>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    current_level: Res<CurrentLevel>,
+    levels: Res<Assets<LevelAsset>>,
     mut timer: ResMut<SelectionTimer>,
+    mut crates_spawned: ResMut<CratesSpawned>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<Tuning>,
+    difficulty: Res<DifficultyCurve>,
     time: Res<Time>,
 ) {
-    let window: &Window = window_query.get_single().unwrap();
-    let window_width = window.width();
-    let random_x = rand::random_range(-window_width / 2.0..window_width / 2.0);
-    let frame_size = Vec2::new(51.2, 51.2);
-    if timer.0.tick(time.delta()).just_finished() {
-        let random_color = match rand::random_range(0..=2) {
+    let Some(level) = levels.get(&current_level.handle) else {
+        return;
+    };
+    if level.crate_zones.is_empty() {
+        return;
+    }
+    // A faster spawn rate runs the timer faster rather than shortening the round's interval
+    let spawn_rate = tuning.spawn_rate.max(0.0) * difficulty.spawn_rate(&tuning.survival);
+    let delta = time.delta().mul_f32(spawn_rate);
+    if timer.0.tick(delta).just_finished() {
+        let zone = &level.crate_zones[rng.random_range(0..level.crate_zones.len())];
+        let spawn_point = zone.random_point(&mut rng);
+        let random_color = match rng.random_range(0..=2) {
             0 => PlatformLayer::Red,
             1 => PlatformLayer::Blue,
             _ => PlatformLayer::Green,
         };
-        commands.spawn((
-            WackyCrate,
-            Sprite {
-                image: image_assets.wacky_crate.clone(),
-                color: random_color.get_rgb_color(),
-                ..Default::default()
-            },
-            Transform::from_xyz(random_x, window.height() + 100.0, 0.0)
-                .with_scale(Vec3::new(0.1, 0.1, 1.0)),
-            SpriteSize { frame_size },
-            RigidBody::Dynamic,
-            Collider::rectangle(512.0, 512.0),
-            ColliderDensity(10.0),
-            CollisionLayers::new(random_color.clone(), random_color)
-        ));
+        spawn_wacky_crate(
+            &mut commands,
+            &image_assets,
+            &tuning,
+            &mut crates_spawned,
+            &difficulty,
+            spawn_point,
+            random_color,
+        );
+    }
+}
 
+/// Spawns the next crate of the round at `position`, collidable with platforms of `color`.
+pub fn spawn_wacky_crate(
+    commands: &mut Commands,
+    image_assets: &ImageAssets,
+    tuning: &Tuning,
+    crates_spawned: &mut CratesSpawned,
+    difficulty: &DifficultyCurve,
+    position: Vec2,
+    color: PlatformLayer,
+) {
+    let curve_step = difficulty.step_for_crate(crates_spawned.0);
+    let frame_size = Vec2::new(51.2, 51.2);
+    commands.spawn((
+        WackyCrate,
+        Sprite {
+            image: image_assets.wacky_crate.clone(),
+            color: color.get_rgb_color(),
+            ..Default::default()
+        },
+        Transform::from_xyz(position.x, position.y, 0.0).with_scale(Vec3::new(0.1, 0.1, 1.0)),
+        SpriteSize { frame_size },
+        RigidBody::Dynamic,
+        Collider::rectangle(512.0, 512.0),
+        ColliderDensity(10.0),
+        TransformInterpolation,
+        CollisionLayers::new(color.clone(), color.clone()),
+        LayerVisual {
+            layer: color,
+            size: Vec2::splat(512.0),
+        },
+        CrateRisk {
+            index: crates_spawned.0,
+            curve_step,
+            explosion_chance: tuning.explosion.chance_for(curve_step),
+        },
+    ));
+    crates_spawned.0 += 1;
+}
+
+/// Re-evaluates the explosion curve for crates already in play after a tuning change.
+pub fn apply_crate_tuning(tuning: Res<Tuning>, mut query: Query<&mut CrateRisk>) {
+    for mut risk in query.iter_mut() {
+        risk.explosion_chance = tuning.explosion.chance_for(risk.curve_step);
     }
 }
+
+pub fn reset_crate_count(mut crates_spawned: ResMut<CratesSpawned>) {
+    crates_spawned.0 = 0;
+}
+
+/// Picks this round's crate spawn interval, between half a second and three seconds.
+pub fn reset_spawn_timer(mut timer: ResMut<SelectionTimer>, mut rng: ResMut<GameRng>) {
+    let random_time = Duration::from_millis(rng.random_range(500..3000));
+    timer.0 = Timer::new(random_time, TimerMode::Repeating);
+}
@@ -1,22 +1,54 @@
 use super::crate_spawner::*;
+use super::level_asset::*;
 use super::platform_spawner::*;
-use crate::GameState;
+use super::play_area::*;
+use crate::data::ron_asset::RonAssetAppExt;
+use crate::data::tuning::Tuning;
+use crate::engine::player_physics::collision_system;
+use crate::engine::simulation::seed_round;
+use crate::{GameState, InGameState};
 use bevy::prelude::*;
-use std::time::Duration;
 
 #[derive(Resource)]
 pub struct SelectionTimer(pub Timer);
 
+/// Loads and builds the selected level and drops crates into it.
 pub struct SpawnEnvironmentsPlugin;
 
 impl Plugin for SpawnEnvironmentsPlugin {
     fn build(&self, app: &mut App) {
-        let random_time = Duration::from_millis(rand::random_range(500..3000) as u64);
         app.insert_resource(SelectionTimer(Timer::from_seconds(
-            random_time.as_secs_f32(),
+            1.0,
             TimerMode::Repeating,
         )))
-        .add_systems(OnEnter(GameState::InGame), spawn_ground)
-        .add_systems(Update, spawn_crate.run_if(in_state(GameState::InGame)));
+        .init_ron_asset::<LevelAsset>()
+        .init_resource::<LevelCatalog>()
+        .init_resource::<SelectedLevel>()
+        .init_resource::<CratesSpawned>()
+        .init_resource::<PlayArea>()
+        .add_systems(OnEnter(GameState::AssetLoading), load_selected_level)
+        .add_systems(
+            OnEnter(GameState::InGame),
+            reset_spawn_timer.after(seed_round),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                (spawn_level, reload_level, spawn_crate)
+                    .chain()
+                    .run_if(resource_exists::<CurrentLevel>),
+                (confine_players, despawn_fallen_crates).after(collision_system),
+            )
+                .run_if(in_state(InGameState::Running)),
+        )
+        .add_systems(Update, fit_play_area_to_window)
+        .add_systems(
+            Update,
+            apply_crate_tuning.run_if(in_state(GameState::InGame).and(resource_changed::<Tuning>)),
+        )
+        .add_systems(
+            OnExit(GameState::InGame),
+            (despawn_level, reset_crate_count),
+        );
     }
 }
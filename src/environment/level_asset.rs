@@ -0,0 +1,116 @@
+use super::platform_spawner::PlatformLayer;
+use crate::data::ron_asset::RonAsset;
+use crate::engine::simulation::GameRng;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// An authored stage, loaded from a `*.level.ron` file under `assets/levels`.
+#[derive(Asset, TypePath, Debug, Deserialize)]
+pub struct LevelAsset {
+    pub name: String,
+    pub platforms: Vec<PlatformDef>,
+    pub crate_zones: Vec<SpawnZone>,
+    pub player_starts: Vec<Vec2>,
+    #[serde(default)]
+    pub hazards: Vec<HazardDef>,
+    /// Where the player can bank their at-risk pot.
+    #[serde(default)]
+    pub cash_outs: Vec<CashOutDef>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlatformDef {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub layer: PlatformLayer,
+}
+
+/// Rectangle in which crates may drop in. A zone with `min == max` is a single point.
+#[derive(Debug, Deserialize)]
+pub struct SpawnZone {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl SpawnZone {
+    pub fn random_point(&self, rng: &mut GameRng) -> Vec2 {
+        Vec2::new(
+            random_between(rng, self.min.x, self.max.x),
+            random_between(rng, self.min.y, self.max.y),
+        )
+    }
+}
+
+fn random_between(rng: &mut GameRng, a: f32, b: f32) -> f32 {
+    if a < b {
+        rng.random_range(a..b)
+    } else {
+        a
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HazardDef {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub damage: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CashOutDef {
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+impl RonAsset for LevelAsset {
+    const EXTENSIONS: &'static [&'static str] = &["level.ron"];
+}
+
+pub struct LevelEntry {
+    pub name: &'static str,
+    pub path: &'static str,
+}
+
+/// Levels offered on the mode select screen.
+#[derive(Resource)]
+pub struct LevelCatalog(pub Vec<LevelEntry>);
+
+impl Default for LevelCatalog {
+    fn default() -> Self {
+        Self(vec![
+            LevelEntry {
+                name: "Classic",
+                path: "levels/classic.level.ron",
+            },
+            LevelEntry {
+                name: "Staircase",
+                path: "levels/staircase.level.ron",
+            },
+        ])
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SelectedLevel(pub usize);
+
+/// Handle of the level being played, and whether its entities are in the world yet.
+#[derive(Resource)]
+pub struct CurrentLevel {
+    pub handle: Handle<LevelAsset>,
+    pub spawned: bool,
+}
+
+pub fn load_selected_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    catalog: Res<LevelCatalog>,
+    selected: Res<SelectedLevel>,
+) {
+    let Some(entry) = catalog.0.get(selected.0).or(catalog.0.first()) else {
+        return;
+    };
+    commands.insert_resource(CurrentLevel {
+        handle: asset_server.load(entry.path),
+        spawned: false,
+    });
+}
@@ -1,3 +1,5 @@
 pub mod crate_spawner;
 pub mod environment_plugin;
+pub mod level_asset;
 pub mod platform_spawner;
+pub mod play_area;
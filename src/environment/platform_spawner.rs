@@ -1,9 +1,14 @@
+use super::crate_spawner::WackyCrate;
+use super::level_asset::*;
+use crate::accessibility::palette::LayerVisual;
+use crate::consts;
+use crate::engine::player::Player;
 use avian2d::prelude::*;
 use bevy::prelude::*;
-use crate::consts;
+use serde::{Deserialize, Serialize};
 
 // Define the collision layers
-#[derive(PhysicsLayer, Default, Debug, Clone)]
+#[derive(PhysicsLayer, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PlatformLayer {
     #[default]
     Default,
@@ -18,71 +23,159 @@ impl PlatformLayer {
             PlatformLayer::Red => consts::RED,
             PlatformLayer::Blue => consts::BLUE,
             PlatformLayer::Green => consts::GREEN,
-            _ => consts::WHITE
+            _ => consts::BLACK,
         }
-    }    
+    }
 }
 
+/// Ground the player can stand on, spanning `left..=right` with its top at `level`.
 #[derive(Component, Debug)]
 pub struct Platform {
     pub level: f32, // Represents the Y-level of the ground
     pub layer: PlatformLayer,
+    pub left: f32,
+    pub right: f32,
+}
+
+impl Platform {
+    pub fn spans(&self, x: f32) -> bool {
+        x >= self.left && x <= self.right
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Hazard {
+    pub damage: u32,
+    pub size: Vec2,
+}
+
+/// Zone where the player's pot is banked into their score.
+#[derive(Component, Debug)]
+pub struct CashOutStation {
+    pub size: Vec2,
+}
+
+/// Instantiates the current level once its asset has finished loading.
+pub fn spawn_level(
+    mut commands: Commands,
+    mut current_level: ResMut<CurrentLevel>,
+    levels: Res<Assets<LevelAsset>>,
+    mut player_query: Query<&mut Transform, With<Player>>,
+) {
+    if current_level.spawned {
+        return;
+    }
+    let Some(level) = levels.get(&current_level.handle) else {
+        return;
+    };
+    info!("Loaded level {}", level.name);
+
+    spawn_level_geometry(&mut commands, level);
+    if let Some(start) = level.player_starts.first() {
+        for mut transform in player_query.iter_mut() {
+            transform.translation = start.extend(transform.translation.z);
+        }
+    }
+    current_level.spawned = true;
 }
 
-pub fn spawn_ground(mut commands: Commands) {
+/// Rebuilds platforms and hazards in place when the current level file is edited.
+/// The player and any crates already in play are left where they are.
+pub fn reload_level(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<LevelAsset>>,
+    current_level: Res<CurrentLevel>,
+    levels: Res<Assets<LevelAsset>>,
+    geometry_query: Query<Entity, Or<(With<Platform>, With<Hazard>, With<CashOutStation>)>>,
+) {
+    let modified = events
+        .read()
+        .any(|event| event.is_modified(&current_level.handle));
+    if !modified || !current_level.spawned {
+        return;
+    }
+    let Some(level) = levels.get(&current_level.handle) else {
+        return;
+    };
+    for entity in geometry_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_level_geometry(&mut commands, level);
+    info!("Reloaded level {}", level.name);
+}
 
-    // Red layer
-    commands
-        .spawn((
+fn spawn_level_geometry(commands: &mut Commands, level: &LevelAsset) {
+    for platform in &level.platforms {
+        spawn_platform(commands, platform);
+    }
+    for hazard in &level.hazards {
+        commands.spawn((
             Sprite {
-                color: consts::RED,
-                custom_size: Some(Vec2::new(4000.0, 20.0)), // Adjust width to fit screen
+                color: consts::HAZARD,
+                custom_size: Some(hazard.size),
                 ..default()
             },
-            Transform::from_xyz(0.0, -150.0, 0.0),
-            RigidBody::Static,
-            Collider::rectangle(4000.0, 20.0),
-            CollisionLayers::new(PlatformLayer::Red, PlatformLayer::Red)
-        ))
-        .insert(Platform {
-            level: -140.0,
-            layer: PlatformLayer::Red,
-        }); // Set ground level
+            Transform::from_translation(hazard.position.extend(0.0)),
+            Collider::rectangle(hazard.size.x, hazard.size.y),
+            Sensor,
+            Hazard {
+                damage: hazard.damage,
+                size: hazard.size,
+            },
+        ));
+    }
+    for station in &level.cash_outs {
+        commands.spawn((
+            Sprite {
+                color: consts::CASH_OUT,
+                custom_size: Some(station.size),
+                ..default()
+            },
+            Transform::from_translation(station.position.extend(0.0)),
+            CashOutStation { size: station.size },
+        ));
+    }
+}
 
-        // Blue layer
-    commands
-    .spawn((
+fn spawn_platform(commands: &mut Commands, platform: &PlatformDef) {
+    let half_size = platform.size / 2.0;
+    commands.spawn((
         Sprite {
-            color: consts::BLUE,
-            custom_size: Some(Vec2::new(4000.0, 20.0)), // Adjust width to fit screen
+            color: platform.layer.get_rgb_color(),
+            custom_size: Some(platform.size),
             ..default()
         },
-        Transform::from_xyz(0.0, -200.0, 0.0),
+        Transform::from_translation(platform.position.extend(0.0)),
         RigidBody::Static,
-        Collider::rectangle(4000.0, 20.0),
-        CollisionLayers::new(PlatformLayer::Blue, PlatformLayer::Blue)
-    ))
-    .insert(Platform {
-        level: -190.0,
-        layer: PlatformLayer::Blue,
-    }); // Set ground level
+        Collider::rectangle(platform.size.x, platform.size.y),
+        CollisionLayers::new(platform.layer.clone(), platform.layer.clone()),
+        Platform {
+            level: platform.position.y + half_size.y, // Set ground level to the top edge
+            layer: platform.layer.clone(),
+            left: platform.position.x - half_size.x,
+            right: platform.position.x + half_size.x,
+        },
+        LayerVisual {
+            layer: platform.layer.clone(),
+            size: platform.size,
+        },
+    ));
+}
 
-    // Green layer
-    commands
-        .spawn((
-            Sprite {
-                color: consts::GREEN,
-                custom_size: Some(Vec2::new(4000.0, 20.0)), // Adjust width to fit screen
-                ..default()
-            },
-            Transform::from_xyz(0.0, -250.0, 0.0),
-            RigidBody::Static,
-            Collider::rectangle(4000.0, 20.0),
-            CollisionLayers::new(PlatformLayer::Green, PlatformLayer::Green)
-        ))
-        .insert(Platform {
-            level: -240.0,
-            layer: PlatformLayer::Green,
-        }); // Set ground level
-        
+pub fn despawn_level(
+    mut commands: Commands,
+    query: Query<
+        Entity,
+        Or<(
+            With<Platform>,
+            With<Hazard>,
+            With<CashOutStation>,
+            With<WackyCrate>,
+        )>,
+    >,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<CurrentLevel>();
 }
@@ -0,0 +1,56 @@
+use super::crate_spawner::WackyCrate;
+use crate::engine::player::Player;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Visible extent of the world, centred on the origin. Follows the primary window when
+/// there is one and stays at its configured size when running headless.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct PlayArea {
+    pub size: Vec2,
+}
+
+impl Default for PlayArea {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(1280.0, 720.0),
+        }
+    }
+}
+
+impl PlayArea {
+    pub fn half_extents(&self) -> Vec2 {
+        self.size / 2.0
+    }
+}
+
+pub fn fit_play_area_to_window(
+    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut play_area: ResMut<PlayArea>,
+) {
+    if let Ok(window) = window_query.get_single() {
+        play_area.size = Vec2::new(window.width(), window.height());
+    }
+}
+
+/// Keeps players horizontally inside the play area.
+pub fn confine_players(play_area: Res<PlayArea>, mut players: Query<&mut Transform, With<Player>>) {
+    let half_width = play_area.half_extents().x;
+    for mut transform in players.iter_mut() {
+        transform.translation.x = transform.translation.x.clamp(-half_width, half_width);
+    }
+}
+
+/// Removes crates that fell off their platform and out of the bottom of the play area.
+pub fn despawn_fallen_crates(
+    mut commands: Commands,
+    play_area: Res<PlayArea>,
+    crates: Query<(Entity, &Transform), With<WackyCrate>>,
+) {
+    let floor = -play_area.size.y;
+    for (entity, transform) in crates.iter() {
+        if transform.translation.y < floor {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::game_ui::focus_navigation::BackButton;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::{panel, spawn_banner, spawn_text_button};
+use crate::localization::localized_text::LocalizedText;
+use crate::GameState;
+use bevy::prelude::*;
+
+/// What each bundled asset is, as a string table key, and who made it. Names are shown
+/// as written.
+const CREDITS: [(&str, &str); 3] = [
+    ("credits.fonts", "Debrosee, Freedom, Glowdex, Inflateptx"),
+    ("credits.interface", "Kenney - UI Pack: Adventure"),
+    (
+        "credits.engine",
+        "Bevy, avian2d, bevy_asset_loader, bevy_egui",
+    ),
+];
+
+#[derive(Component)]
+pub enum CreditsButtons {
+    Back,
+}
+
+#[derive(Component)]
+pub struct OnCreditsScreen;
+
+pub fn setup_credits(mut commands: Commands, theme: Res<UiTheme>) {
+    commands
+        .spawn((
+            Node {
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                padding: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+            panel(&theme),
+            OnCreditsScreen,
+        ))
+        .with_children(|parent| {
+            spawn_banner(parent, &theme, "menu.credits");
+            for (role, names) in CREDITS {
+                parent.spawn((Text::default(), LocalizedText::new(role), theme.text(26.0)));
+                parent.spawn((Text::new(names), theme.text(20.0)));
+            }
+            spawn_text_button(
+                parent,
+                &theme,
+                "menu.back",
+                250.0,
+                (CreditsButtons::Back, BackButton),
+            );
+        });
+}
+
+pub fn credits_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &CreditsButtons),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut ui_events: EventWriter<UiEvent>,
+) {
+    for (interaction, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
+                match button {
+                    CreditsButtons::Back => game_state.set(GameState::MainMenu),
+                }
+            }
+            Interaction::Hovered => {
+                ui_events.send(UiEvent::ButtonHovered);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+pub fn cleanup_credits(mut commands: Commands, query: Query<Entity, With<OnCreditsScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
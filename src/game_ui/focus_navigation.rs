@@ -0,0 +1,175 @@
+use crate::consts;
+use bevy::prelude::*;
+
+/// The button Enter or the gamepad's A button activates. Moved with the arrow keys or
+/// D-pad, and follows the mouse when a button is hovered.
+#[derive(Component)]
+pub struct Focused;
+
+/// Button that Escape or the gamepad's B button activates on its screen, e.g. "Back".
+#[derive(Component)]
+pub struct BackButton;
+
+#[derive(Resource, Default)]
+pub struct FocusNavigation {
+    /// Set once keys or a gamepad move the focus, so new screens focus their first button.
+    active: bool,
+    /// Button pressed by the last activation, released again on the next frame.
+    pressed: Option<Entity>,
+}
+
+fn focus_outline() -> Outline {
+    Outline::new(Val::Px(4.0), Val::Px(3.0), consts::FOCUS_OUTLINE)
+}
+
+fn move_focus(commands: &mut Commands, from: Option<Entity>, to: Entity) {
+    if from == Some(to) {
+        return;
+    }
+    if let Some(from) = from {
+        if let Some(mut entity) = commands.get_entity(from) {
+            entity.remove::<(Focused, Outline)>();
+        }
+    }
+    commands.entity(to).insert((Focused, focus_outline()));
+}
+
+fn just_pressed(
+    keyboard: &ButtonInput<KeyCode>,
+    gamepads: &Query<&Gamepad>,
+    key: KeyCode,
+    button: GamepadButton,
+) -> bool {
+    keyboard.just_pressed(key) || gamepads.iter().any(|pad| pad.just_pressed(button))
+}
+
+/// Direction pressed this frame on the keyboard or any gamepad's D-pad, in UI space
+/// where y grows downwards.
+fn navigation_direction(keyboard: &ButtonInput<KeyCode>, gamepads: &Query<&Gamepad>) -> Vec2 {
+    let pressed = |key, button| just_pressed(keyboard, gamepads, key, button);
+    let mut direction = Vec2::ZERO;
+    if pressed(KeyCode::ArrowUp, GamepadButton::DPadUp) {
+        direction.y -= 1.0;
+    }
+    if pressed(KeyCode::ArrowDown, GamepadButton::DPadDown) {
+        direction.y += 1.0;
+    }
+    if pressed(KeyCode::ArrowLeft, GamepadButton::DPadLeft) {
+        direction.x -= 1.0;
+    }
+    if pressed(KeyCode::ArrowRight, GamepadButton::DPadRight) {
+        direction.x += 1.0;
+    }
+    direction
+}
+
+/// Moves the focus to hovered buttons, to the nearest button in the pressed direction,
+/// and onto the first button of a new screen once navigation is in use.
+pub fn navigate_focus(
+    mut commands: Commands,
+    mut navigation: ResMut<FocusNavigation>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    buttons: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &ComputedNode,
+            &InheritedVisibility,
+        ),
+        With<Button>,
+    >,
+    hovered: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+    focused: Query<Entity, With<Focused>>,
+) {
+    let current = focused.iter().next();
+    if let Some((entity, _)) = hovered
+        .iter()
+        .find(|(_, interaction)| **interaction == Interaction::Hovered)
+    {
+        move_focus(&mut commands, current, entity);
+        return;
+    }
+    let direction = navigation_direction(&keyboard, &gamepads);
+    if direction != Vec2::ZERO {
+        navigation.active = true;
+    }
+    if !navigation.active {
+        return;
+    }
+    // Buttons that are laid out and shown
+    let candidates = buttons
+        .iter()
+        .filter(|(_, _, node, visibility)| visibility.get() && node.size() != Vec2::ZERO)
+        .map(|(entity, transform, _, _)| (entity, transform.translation().truncate()));
+    let Some(origin) = current.and_then(|entity| buttons.get(entity).ok()) else {
+        // Nothing focused yet: start at the top-left button
+        let first =
+            candidates.min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        if let Some((entity, _)) = first {
+            move_focus(&mut commands, current, entity);
+        }
+        return;
+    };
+    if direction == Vec2::ZERO {
+        return;
+    }
+    let origin = origin.1.translation().truncate();
+    // Prefer buttons straight ahead over ones further off to the side
+    let next = candidates
+        .filter_map(|(entity, position)| {
+            let offset = position - origin;
+            let along = offset.dot(direction);
+            (along > 1.0).then(|| (entity, along + 2.0 * offset.perp_dot(direction).abs()))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b));
+    if let Some((entity, _)) = next {
+        move_focus(&mut commands, current, entity);
+    }
+}
+
+/// Ends the press started by `activate_focused` one frame earlier. Mouse presses are
+/// released by Bevy itself.
+pub fn release_activated_button(
+    mut navigation: ResMut<FocusNavigation>,
+    mut interactions: Query<&mut Interaction>,
+) {
+    if let Some(entity) = navigation.pressed.take() {
+        if let Ok(mut interaction) = interactions.get_mut(entity) {
+            if *interaction == Interaction::Pressed {
+                *interaction = Interaction::None;
+            }
+        }
+    }
+}
+
+/// Presses the focused button on Enter or A, and the screen's back button on Escape or B.
+/// Runs after Bevy's UI focus update, so the screens' button systems see the press like a
+/// click.
+pub fn activate_focused(
+    mut navigation: ResMut<FocusNavigation>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focused: Query<Entity, With<Focused>>,
+    back_buttons: Query<(Entity, &InheritedVisibility), With<BackButton>>,
+    mut interactions: Query<&mut Interaction>,
+) {
+    let pressed = |key, button| just_pressed(&keyboard, &gamepads, key, button);
+    let target = if pressed(KeyCode::Enter, GamepadButton::South) {
+        focused.iter().next()
+    } else if pressed(KeyCode::Escape, GamepadButton::East) {
+        back_buttons
+            .iter()
+            .find(|(_, visibility)| visibility.get())
+            .map(|(entity, _)| entity)
+    } else {
+        None
+    };
+    let Some(entity) = target else {
+        return;
+    };
+    if let Ok(mut interaction) = interactions.get_mut(entity) {
+        *interaction = Interaction::Pressed;
+        navigation.pressed = Some(entity);
+    }
+}
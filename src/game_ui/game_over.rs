@@ -0,0 +1,236 @@
+use crate::data::tuning::Tuning;
+use crate::engine::game_mode::GameMode;
+use crate::engine::round_end::RoundResult;
+use crate::engine::simulation::NextRoundSeed;
+use crate::game_ui::focus_navigation::BackButton;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::{panel, spawn_banner, spawn_text_button};
+use crate::localization::string_table::StringTable;
+use crate::profile::leaderboard::*;
+use crate::profile::profiles::Profiles;
+use crate::replay::playback::Playback;
+use crate::GameState;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub enum GameOverButtons {
+    PlayAgain,
+    /// Plays the same seed and level again, to beat the seed's leaderboard.
+    RetrySeed,
+    Menu,
+}
+
+#[derive(Component)]
+pub struct OnGameOverScreen;
+
+/// Column of ranked scores under a heading.
+fn spawn_board(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    strings: &StringTable,
+    heading: String,
+    lines: impl Iterator<Item = String>,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(6.0),
+            min_width: Val::Px(300.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((Text::new(heading), theme.text(26.0)));
+            let mut empty = true;
+            for (rank, line) in lines.take(LEADERBOARD_SHOWN).enumerate() {
+                parent.spawn((Text::new(format!("{}. {line}", rank + 1)), theme.text(20.0)));
+                empty = false;
+            }
+            if empty {
+                parent.spawn((
+                    Text::new(strings.get("leaderboard.empty")),
+                    theme.text(20.0),
+                ));
+            }
+        });
+}
+
+pub fn setup_game_over(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    strings: Res<StringTable>,
+    result: Option<Res<RoundResult>>,
+    leaderboard: Res<Leaderboard>,
+    profiles: Res<Profiles>,
+    tuning: Res<Tuning>,
+) {
+    let Some(result) = result else {
+        return;
+    };
+    let title = if result.out_of_time {
+        "game_over.time_up"
+    } else {
+        "game_over.title"
+    };
+    commands
+        .spawn((
+            Node {
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(15.0),
+                padding: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+            panel(&theme),
+            OnGameOverScreen,
+        ))
+        .with_children(|parent| {
+            spawn_banner(parent, &theme, title);
+            parent.spawn((
+                Text::new(strings.format(
+                    "game_over.score",
+                    &[
+                        ("mode", strings.get(result.mode.label_key()).to_string()),
+                        ("score", result.score.to_string()),
+                    ],
+                )),
+                theme.text(32.0),
+            ));
+            let curve = &tuning.explosion;
+            parent.spawn((
+                Text::new(strings.format(
+                    "game_over.curve",
+                    &[
+                        ("step", result.curve_step.to_string()),
+                        (
+                            "chance",
+                            format!("{:.0}", curve.chance_for(result.curve_step) * 100.0),
+                        ),
+                        (
+                            "progress",
+                            format!("{:.0}", curve.progress(result.curve_step) * 100.0),
+                        ),
+                    ],
+                )),
+                theme.text(20.0),
+            ));
+            if result.mode == GameMode::TimeAttack {
+                let name = profiles.current_name();
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(40.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        spawn_board(
+                            parent,
+                            &theme,
+                            &strings,
+                            strings.format("leaderboard.profile", &[("name", name.to_string())]),
+                            leaderboard.for_profile(name).map(|entry| {
+                                strings.format(
+                                    "leaderboard.profile_line",
+                                    &[
+                                        ("score", entry.score.to_string()),
+                                        ("seed", entry.seed.to_string()),
+                                    ],
+                                )
+                            }),
+                        );
+                        spawn_board(
+                            parent,
+                            &theme,
+                            &strings,
+                            strings.get("leaderboard.seed").to_string(),
+                            leaderboard
+                                .for_seed(result.seed, &result.level)
+                                .map(|entry| {
+                                    strings.format(
+                                        "leaderboard.seed_line",
+                                        &[
+                                            ("name", entry.profile.clone()),
+                                            ("score", entry.score.to_string()),
+                                        ],
+                                    )
+                                }),
+                        );
+                    });
+            }
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(20.0),
+                    margin: UiRect::top(Val::Px(15.0)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "game_over.menu",
+                        250.0,
+                        (GameOverButtons::Menu, BackButton),
+                    );
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "game_over.retry_seed",
+                        250.0,
+                        GameOverButtons::RetrySeed,
+                    );
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "game_over.play_again",
+                        250.0,
+                        GameOverButtons::PlayAgain,
+                    );
+                });
+        });
+}
+
+pub fn game_over_button_system(
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (&Interaction, &GameOverButtons),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut next_seed: ResMut<NextRoundSeed>,
+    result: Option<Res<RoundResult>>,
+    mut ui_events: EventWriter<UiEvent>,
+) {
+    for (interaction, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
+                match button {
+                    GameOverButtons::PlayAgain => {
+                        // After watching a replay, the next round is the player's own
+                        commands.remove_resource::<Playback>();
+                        game_state.set(GameState::AssetLoading);
+                    }
+                    GameOverButtons::RetrySeed => {
+                        commands.remove_resource::<Playback>();
+                        next_seed.0 = result.as_ref().map(|result| result.seed);
+                        game_state.set(GameState::AssetLoading);
+                    }
+                    GameOverButtons::Menu => game_state.set(GameState::MainMenu),
+                }
+            }
+            Interaction::Hovered => {
+                ui_events.send(UiEvent::ButtonHovered);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+pub fn cleanup_game_over(mut commands: Commands, query: Query<Entity, With<OnGameOverScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
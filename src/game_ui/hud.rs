@@ -0,0 +1,130 @@
+use crate::engine::difficulty::{CurvePace, DifficultyCurve};
+use crate::engine::game_mode::PredictionCalls;
+use crate::engine::player::{Player, PlayerStatus};
+use crate::engine::time_attack::RoundClock;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::{panel, spawn_progress_bar, ProgressBar};
+use crate::localization::string_table::StringTable;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct HudText;
+
+#[derive(Component)]
+pub struct HpBar;
+
+/// Time left in a Time Attack round, or the difficulty reached in Survival; empty in
+/// other modes.
+#[derive(Component)]
+pub struct HudRoundInfo;
+
+#[derive(Component)]
+pub struct OnHud;
+
+fn hud_text(
+    status: &PlayerStatus,
+    calls: Option<&PredictionCalls>,
+    strings: &StringTable,
+) -> String {
+    let mut text = strings.format(
+        "hud.status",
+        &[
+            ("score", status.points.to_string()),
+            ("pot", status.pot.to_string()),
+            ("streak", status.streak.to_string()),
+            ("hp", status.hp.to_string()),
+        ],
+    );
+    // Prediction mode adds the pending call and how many calls were right
+    if let Some(calls) = calls {
+        let call = match calls.pending {
+            Some(true) => "hud.call.explode",
+            Some(false) => "hud.call.safe",
+            None => "hud.call.none",
+        };
+        text.push('\n');
+        text.push_str(&strings.format(
+            "hud.prediction",
+            &[
+                ("call", strings.get(call).to_string()),
+                ("correct", calls.correct.to_string()),
+                ("made", calls.made.to_string()),
+            ],
+        ));
+    }
+    text
+}
+
+fn hp_fraction(status: &PlayerStatus) -> f32 {
+    status.hp as f32 / status.max_hp.max(1) as f32
+}
+
+/// Score line and hp bar in the top left corner during a round.
+pub fn setup_hud(mut commands: Commands, theme: Res<UiTheme>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(15.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::axes(Val::Px(20.0), Val::Px(14.0)),
+                ..default()
+            },
+            panel(&theme),
+            OnHud,
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::default(), theme.text(26.0), HudText));
+            spawn_progress_bar(parent, &theme, 1.0, 240.0, HpBar);
+            parent.spawn((Text::default(), theme.text(26.0), HudRoundInfo));
+        });
+}
+
+pub fn update_hud(
+    strings: Res<StringTable>,
+    players: Query<(Ref<PlayerStatus>, Option<Ref<PredictionCalls>>), With<Player>>,
+    mut texts: Query<(&mut Text, Ref<HudText>)>,
+    mut hp_bars: Query<&mut ProgressBar, With<HpBar>>,
+) {
+    let Some((status, calls)) = players.iter().next() else {
+        return;
+    };
+    let calls_changed = calls.as_ref().is_some_and(|calls| calls.is_changed());
+    for (mut text, marker) in texts.iter_mut() {
+        if status.is_changed() || calls_changed || strings.is_changed() || marker.is_added() {
+            text.0 = hud_text(&status, calls.as_deref(), &strings);
+        }
+    }
+    for mut bar in hp_bars.iter_mut() {
+        bar.set_if_neq(ProgressBar(hp_fraction(&status)));
+    }
+}
+
+pub fn update_round_info(
+    strings: Res<StringTable>,
+    clock: Option<Res<RoundClock>>,
+    difficulty: Res<DifficultyCurve>,
+    mut texts: Query<&mut Text, With<HudRoundInfo>>,
+) {
+    let text = if let Some(clock) = clock {
+        let seconds = clock.0.remaining_secs().ceil() as u32;
+        strings.format("hud.time_left", &[("seconds", seconds.to_string())])
+    } else if difficulty.pace == CurvePace::Score {
+        strings.format("hud.difficulty", &[("step", difficulty.step.to_string())])
+    } else {
+        String::new()
+    };
+    for mut line in texts.iter_mut() {
+        if line.0 != text {
+            line.0 = text.clone();
+        }
+    }
+}
+
+pub fn cleanup_hud(mut commands: Commands, query: Query<Entity, With<OnHud>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,76 @@
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::*;
+use crate::localization::localized_text::LocalizedText;
+use crate::GameState;
+use bevy::prelude::*;
+use iyes_progress::prelude::*;
+
+/// Probability facts shown while a round loads, as string table keys.
+const TIPS: [&str; 8] = [
+    "tip.independent",
+    "tip.long_run",
+    "tip.one_in_ten",
+    "tip.streaks",
+    "tip.expected_value",
+    "tip.gamblers_fallacy",
+    "tip.rising_odds",
+    "tip.bank_early",
+];
+
+#[derive(Component)]
+pub struct LoadingBar;
+
+#[derive(Component)]
+pub struct OnLoadingScreen;
+
+pub fn setup_loading_screen(mut commands: Commands, theme: Res<UiTheme>) {
+    // Not gameplay, so the tip doesn't come from the seeded round RNG
+    let tip = TIPS[rand::random_range(0..TIPS.len())];
+    commands
+        .spawn((
+            Node {
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                padding: UiRect::all(Val::Px(30.0)),
+                max_width: Val::Px(700.0),
+                ..default()
+            },
+            panel(&theme),
+            OnLoadingScreen,
+        ))
+        .with_children(|parent| {
+            spawn_banner(parent, &theme, "loading.title");
+            spawn_progress_bar(parent, &theme, 0.0, 500.0, LoadingBar);
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("loading.tip"),
+                theme.text(22.0),
+            ));
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(tip),
+                theme.text(24.0),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+        });
+}
+
+pub fn update_loading_bar(
+    progress: Res<ProgressTracker<GameState>>,
+    mut bars: Query<&mut ProgressBar, With<LoadingBar>>,
+) {
+    let Progress { done, total } = progress.get_global_progress();
+    let fraction = done as f32 / total.max(1) as f32;
+    for mut bar in bars.iter_mut() {
+        bar.set_if_neq(ProgressBar(fraction));
+    }
+}
+
+pub fn cleanup_loading_screen(mut commands: Commands, query: Query<Entity, With<OnLoadingScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
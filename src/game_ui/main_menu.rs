@@ -1,143 +1,212 @@
+use crate::animations::asset_loader::LoadFailure;
 use crate::consts;
+use crate::engine::assist::AssistSettings;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::{panel, spawn_text_button};
+use crate::localization::string_table::StringTable;
+use crate::profile::profiles::Profiles;
+use crate::replay::playback::start_playback;
+use crate::replay::replay_file::Replay;
 use crate::GameState;
 use bevy::prelude::*;
 
+/// Seconds the title takes to drop into place when the menu opens.
+const TITLE_DROP_SECS: f32 = 0.6;
+const TITLE_SIZE: f32 = 360.0;
+
 #[derive(Component)]
 pub enum MenuButtons {
     Play,
+    Profiles,
     Settings,
+    Stats,
+    WatchReplay,
+    Credits,
+    Quit,
 }
 
 #[derive(Component)]
 pub struct OnMainMenuScreen;
 
-pub fn setup_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let menu_font = asset_server.load("fonts/Glowdex.ttf");
-    let title_img = asset_server.load("WACKY_3.png");
+/// Title image that drops in when the menu opens and then gently bobs.
+#[derive(Component, Default)]
+pub struct TitleAnimation {
+    elapsed: f32,
+}
+
+pub fn spawn_camera(mut commands: Commands) {
     commands.spawn(Camera2d);
+}
+
+pub fn setup_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<UiTheme>,
+    profiles: Res<Profiles>,
+    strings: Res<StringTable>,
+    mut failure: ResMut<LoadFailure>,
+) {
+    let title_img = asset_server.load("WACKY_3.png");
     commands
         .spawn((
             Node {
-                align_content: AlignContent::Center,
-                align_self: AlignSelf::Center,
-                justify_self: JustifySelf::Center,
-                position_type: PositionType::Relative,
-                flex_wrap: FlexWrap::NoWrap,
-                flex_direction: FlexDirection::Column, // Stack items vertically
-                justify_content: JustifyContent::FlexStart, // Align from top
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
-                // Add spacing between items
-                row_gap: Val::Px(10.0),
-                ..Default::default()
+                column_gap: Val::Px(60.0),
+                ..default()
             },
             OnMainMenuScreen,
         ))
         .with_children(|parent| {
-            // Title Image
             parent.spawn((
                 ImageNode {
                     image: title_img,
                     ..default()
                 },
                 Node {
-                    width: Val::Px(400.0),
-                    height: Val::Px(400.0),
-                    margin: UiRect::top(Val::VMin(5.)),
+                    width: Val::Px(TITLE_SIZE),
+                    height: Val::Px(TITLE_SIZE),
                     ..default()
                 },
+                TitleAnimation::default(),
             ));
-            // Start Game Button
-            parent
-                .spawn((
-                    Button,
-                    Node {
-                        width: Val::Px(250.0),
-                        height: Val::Px(65.0),
-                        border: UiRect::all(Val::Px(5.0)),
-                        // horizontally center child text
-                        justify_content: JustifyContent::Center,
-                        // vertically center child text
-                        align_items: AlignItems::Center,
-                        ..default()
-                    },
-                    BorderColor(consts::BUTTON_BORDER),
-                    BorderRadius::MAX,
-                    BackgroundColor(consts::NORMAL_BUTTON),
-                ))
-                .insert(MenuButtons::Play)
-                .with_children(|parent: &mut ChildBuilder<'_>| {
-                    parent.spawn((
-                        Text::from("Play Game"),
-                        TextFont {
-                            font: menu_font.clone(),
-                            font_size: 30.0,
-                            ..default()
-                        },
-                        TextColor(consts::TEXT_COLOR),
-                    ));
-                });
-            // Game Settings Button
             parent
                 .spawn((
-                    Button,
                     Node {
-                        width: Val::Px(250.0),
-                        height: Val::Px(65.0),
-                        border: UiRect::all(Val::Px(5.0)),
-                        // horizontally center child text
-                        justify_content: JustifyContent::Center,
-                        // vertically center child text
+                        flex_direction: FlexDirection::Column,
                         align_items: AlignItems::Center,
+                        row_gap: Val::Px(10.0),
+                        padding: UiRect::all(Val::Px(30.0)),
                         ..default()
                     },
-                    BorderColor(consts::BUTTON_BORDER),
-                    BorderRadius::MAX,
-                    BackgroundColor(consts::NORMAL_BUTTON),
+                    panel(&theme),
                 ))
-                .insert(MenuButtons::Settings)
                 .with_children(|parent| {
+                    // Explain why the last round didn't start, once
+                    if let Some(error) = failure.0.take() {
+                        parent.spawn((
+                            Text::new(strings.format("loading.failed", &[("error", error)])),
+                            // Keep the theme's font but not its text color
+                            theme.text(20.0).0,
+                            TextColor(consts::LOAD_ERROR),
+                            Node {
+                                max_width: Val::Px(320.0),
+                                ..default()
+                            },
+                        ));
+                    }
+                    // Profile names aren't table keys, so the line is formatted here
                     parent.spawn((
-                        Text::from("Settings"),
-                        TextFont {
-                            font: menu_font,
-                            font_size: 30.0,
-                            ..default()
-                        },
-                        TextColor(consts::TEXT_COLOR),
+                        Text::new(strings.format(
+                            "menu.profile",
+                            &[("name", profiles.current_name().to_string())],
+                        )),
+                        theme.text(24.0),
                     ));
+                    spawn_text_button(parent, &theme, "menu.play", 250.0, MenuButtons::Play);
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "menu.profiles",
+                        250.0,
+                        MenuButtons::Profiles,
+                    );
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "menu.settings",
+                        250.0,
+                        MenuButtons::Settings,
+                    );
+                    spawn_text_button(parent, &theme, "menu.stats", 250.0, MenuButtons::Stats);
+                    // Watch the last recorded round, if there is one
+                    if Replay::latest().is_some() {
+                        spawn_text_button(
+                            parent,
+                            &theme,
+                            "menu.watch_replay",
+                            250.0,
+                            MenuButtons::WatchReplay,
+                        );
+                    }
+                    spawn_text_button(parent, &theme, "menu.credits", 250.0, MenuButtons::Credits);
+                    spawn_text_button(parent, &theme, "menu.quit", 250.0, MenuButtons::Quit);
                 });
         });
 }
 
 // System to handle button interaction
 pub fn button_interaction_system(
+    mut commands: Commands,
     mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &MenuButtons),
+        (&Interaction, &MenuButtons),
         (Changed<Interaction>, With<Button>),
     >,
     mut game_state: ResMut<NextState<GameState>>,
+    mut ui_events: EventWriter<UiEvent>,
+    mut app_exit: EventWriter<AppExit>,
 ) {
-    for (interaction, mut color, button) in interaction_query.iter_mut() {
+    for (interaction, button) in interaction_query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
                 match button {
                     MenuButtons::Play => {
-                        println!("Play Game Button Clicked"); // Switch to Lobby state
-                        game_state.set(GameState::AssetLoading);
+                        debug!("play pressed");
+                        game_state.set(GameState::ModeSelect);
                     }
+                    MenuButtons::Profiles => game_state.set(GameState::PlayerSelection),
                     MenuButtons::Settings => {
-                        println!("Settings Button Clicked"); // Switch to Lobby state
+                        debug!("settings pressed");
                         game_state.set(GameState::Settings);
                     }
+                    MenuButtons::Stats => game_state.set(GameState::Stats),
+                    MenuButtons::WatchReplay => {
+                        match Replay::latest().map(|path| Replay::load(&path)) {
+                            Some(Ok(replay)) => {
+                                start_playback(&mut commands, replay);
+                                game_state.set(GameState::AssetLoading);
+                            }
+                            Some(Err(error)) => warn!("{error}"),
+                            None => {}
+                        }
+                    }
+                    MenuButtons::Credits => game_state.set(GameState::Credits),
+                    MenuButtons::Quit => {
+                        app_exit.send(AppExit::Success);
+                    }
                 }
             }
             Interaction::Hovered => {
-                *color = consts::HOVERED_BUTTON.into();
-            }
-            Interaction::None => {
-                *color = consts::NORMAL_BUTTON.into();
+                ui_events.send(UiEvent::ButtonHovered);
             }
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Drops the title in from above, then bobs it up and down. Stays still with reduced
+/// motion on.
+pub fn animate_title(
+    time: Res<Time>,
+    assist: Res<AssistSettings>,
+    mut titles: Query<(&mut TitleAnimation, &mut Node)>,
+) {
+    for (mut animation, mut node) in titles.iter_mut() {
+        if assist.reduced_motion {
+            node.top = Val::Px(0.0);
+            continue;
         }
+        animation.elapsed += time.delta_secs();
+        let drop = (animation.elapsed / TITLE_DROP_SECS).min(1.0);
+        // Ease out so the title slows down as it lands
+        let fall = -TITLE_SIZE * (1.0 - drop).powi(3);
+        let bob = (animation.elapsed * 2.0).sin() * 8.0 * drop;
+        node.top = Val::Px(fall + bob);
     }
 }
 
@@ -1,3 +1,18 @@
+pub mod credits;
+pub mod focus_navigation;
+pub mod game_over;
+pub mod hud;
+pub mod loading_screen;
 pub mod main_menu;
+pub mod mode_select;
+pub mod pause_menu;
+pub mod player_selection;
+pub mod replay_controls;
+pub mod rule_editor;
+pub mod screen_transition;
 pub mod settings;
+pub mod stats_screen;
+pub mod ui_events;
 pub mod ui_plugin;
+pub mod ui_theme;
+pub mod widgets;
@@ -0,0 +1,212 @@
+use crate::data::rule_set::*;
+use crate::engine::game_mode::*;
+use crate::environment::level_asset::*;
+use crate::game_ui::focus_navigation::BackButton;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::*;
+use crate::localization::localized_text::LocalizedText;
+use crate::GameState;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub enum ModeSelectButtons {
+    Mode(GameMode),
+    Level(usize),
+    Preset(DifficultyPreset),
+    /// Opens the rule editor.
+    CustomRules,
+    Start,
+    Back,
+}
+
+/// Explanation of the selected mode.
+#[derive(Component)]
+pub struct ModeDescription;
+
+#[derive(Component)]
+pub struct OnModeSelectScreen;
+
+/// Row of choice buttons under a heading.
+fn spawn_choice_row(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    heading: &str,
+    build: impl FnOnce(&mut ChildBuilder),
+) {
+    parent.spawn((
+        Text::default(),
+        LocalizedText::new(heading),
+        theme.text(26.0),
+    ));
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(10.0),
+            ..default()
+        })
+        .with_children(build);
+}
+
+pub fn setup_mode_select(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    catalog: Res<LevelCatalog>,
+    mode: Res<SelectedMode>,
+) {
+    commands
+        .spawn((
+            Node {
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(15.0),
+                padding: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+            panel(&theme),
+            OnModeSelectScreen,
+        ))
+        .with_children(|parent| {
+            spawn_banner(parent, &theme, "modes.title");
+            spawn_choice_row(parent, &theme, "modes.mode", |parent| {
+                for mode in GameMode::ALL {
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        mode.label_key(),
+                        200.0,
+                        ModeSelectButtons::Mode(mode),
+                    );
+                }
+            });
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(mode.0.description_key()),
+                theme.text(20.0),
+                ModeDescription,
+            ));
+            spawn_choice_row(parent, &theme, "modes.level", |parent| {
+                for (index, level) in catalog.0.iter().enumerate() {
+                    // Level names aren't table keys, so they show as written
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        level.name,
+                        200.0,
+                        ModeSelectButtons::Level(index),
+                    );
+                }
+            });
+            spawn_choice_row(parent, &theme, "modes.difficulty", |parent| {
+                for preset in DifficultyPreset::ALL {
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        preset.label_key(),
+                        200.0,
+                        ModeSelectButtons::Preset(preset),
+                    );
+                }
+                spawn_text_button(
+                    parent,
+                    &theme,
+                    "rules.custom",
+                    200.0,
+                    ModeSelectButtons::CustomRules,
+                );
+            });
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(20.0),
+                    margin: UiRect::top(Val::Px(15.0)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "menu.back",
+                        250.0,
+                        (ModeSelectButtons::Back, BackButton),
+                    );
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "modes.start",
+                        250.0,
+                        ModeSelectButtons::Start,
+                    );
+                });
+        });
+}
+
+pub fn mode_select_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &ModeSelectButtons),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut selected_mode: ResMut<SelectedMode>,
+    mut selected_level: ResMut<SelectedLevel>,
+    mut selected_rules: ResMut<SelectedRules>,
+    mut ui_events: EventWriter<UiEvent>,
+) {
+    for (interaction, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
+                match button {
+                    ModeSelectButtons::Mode(mode) => selected_mode.0 = *mode,
+                    ModeSelectButtons::Level(index) => selected_level.0 = *index,
+                    ModeSelectButtons::Preset(preset) => {
+                        selected_rules.0 = RuleChoice::Preset(*preset);
+                    }
+                    ModeSelectButtons::CustomRules => game_state.set(GameState::RuleEditor),
+                    ModeSelectButtons::Start => game_state.set(GameState::AssetLoading),
+                    ModeSelectButtons::Back => game_state.set(GameState::MainMenu),
+                }
+            }
+            Interaction::Hovered => {
+                ui_events.send(UiEvent::ButtonHovered);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Highlights the chosen mode, level and rules and explains the mode.
+pub fn highlight_selections(
+    selected_mode: Res<SelectedMode>,
+    selected_level: Res<SelectedLevel>,
+    selected_rules: Res<SelectedRules>,
+    mut buttons: Query<(&ModeSelectButtons, &mut ThemedButton)>,
+    mut descriptions: Query<&mut LocalizedText, With<ModeDescription>>,
+) {
+    for (button, mut themed) in buttons.iter_mut() {
+        let selected = match button {
+            ModeSelectButtons::Mode(mode) => *mode == selected_mode.0,
+            ModeSelectButtons::Level(index) => *index == selected_level.0,
+            ModeSelectButtons::Preset(preset) => selected_rules.0 == RuleChoice::Preset(*preset),
+            ModeSelectButtons::CustomRules => matches!(selected_rules.0, RuleChoice::Custom(_)),
+            _ => false,
+        };
+        if themed.selected != selected {
+            themed.selected = selected;
+        }
+    }
+    for mut description in descriptions.iter_mut() {
+        let key = selected_mode.0.description_key();
+        if description.0 != key {
+            description.0 = key.to_string();
+        }
+    }
+}
+
+pub fn cleanup_mode_select(mut commands: Commands, query: Query<Entity, With<OnModeSelectScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
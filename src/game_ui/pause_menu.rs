@@ -0,0 +1,158 @@
+use crate::consts;
+use crate::game_ui::focus_navigation::BackButton;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::{panel, spawn_banner, spawn_text_button};
+use crate::replay::playback::Playback;
+use crate::{GameState, InGameState, PauseMenu};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub enum PauseButtons {
+    Resume,
+    Restart,
+    Settings,
+    QuitToMenu,
+}
+
+#[derive(Component)]
+pub struct OnPauseScreen;
+
+/// Escape or Start pauses the round and Start resumes it. Inside the pause menu Escape
+/// and B go back through the screens' back buttons instead.
+pub fn toggle_pause(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    in_game_state: Res<State<InGameState>>,
+    mut next_in_game_state: ResMut<NextState<InGameState>>,
+) {
+    let start = gamepads
+        .iter()
+        .any(|gamepad| gamepad.just_pressed(GamepadButton::Start));
+    match in_game_state.get() {
+        InGameState::Running if start || keyboard_input.just_pressed(KeyCode::Escape) => {
+            next_in_game_state.set(InGameState::Paused)
+        }
+        InGameState::Paused if start => next_in_game_state.set(InGameState::Running),
+        _ => {}
+    }
+}
+
+/// Stops virtual time (animation, spawn and effect timers) and the physics clock so the
+/// round resumes from exactly the same moment.
+pub fn pause_time(
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    virtual_time.pause();
+    physics_time.pause();
+}
+
+/// Restarts both clocks, except virtual time when a replay was already paused before the
+/// pause menu opened, so the replay stays paused.
+pub fn resume_time(
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut physics_time: ResMut<Time<Physics>>,
+    playback: Option<Res<Playback>>,
+) {
+    if !playback.is_some_and(|playback| playback.paused) {
+        virtual_time.unpause();
+    }
+    physics_time.unpause();
+}
+
+pub fn setup_pause_menu(mut commands: Commands, theme: Res<UiTheme>) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(consts::OVERLAY_BG),
+            OnPauseScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(15.0),
+                        padding: UiRect::all(Val::Px(30.0)),
+                        ..default()
+                    },
+                    panel(&theme),
+                ))
+                .with_children(|parent| {
+                    spawn_banner(parent, &theme, "pause.title");
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "pause.resume",
+                        250.0,
+                        (PauseButtons::Resume, BackButton),
+                    );
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "pause.restart",
+                        250.0,
+                        PauseButtons::Restart,
+                    );
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "menu.settings",
+                        250.0,
+                        PauseButtons::Settings,
+                    );
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "pause.quit",
+                        250.0,
+                        PauseButtons::QuitToMenu,
+                    );
+                });
+        });
+}
+
+pub fn pause_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &PauseButtons),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut in_game_state: ResMut<NextState<InGameState>>,
+    mut pause_menu: ResMut<NextState<PauseMenu>>,
+    mut ui_events: EventWriter<UiEvent>,
+) {
+    for (interaction, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
+                match button {
+                    PauseButtons::Resume => in_game_state.set(InGameState::Running),
+                    // Going back through asset loading rebuilds the level and player
+                    PauseButtons::Restart => game_state.set(GameState::AssetLoading),
+                    PauseButtons::Settings => pause_menu.set(PauseMenu::Settings),
+                    PauseButtons::QuitToMenu => game_state.set(GameState::MainMenu),
+                }
+            }
+            Interaction::Hovered => {
+                ui_events.send(UiEvent::ButtonHovered);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+pub fn cleanup_pause_menu(mut commands: Commands, query: Query<Entity, With<OnPauseScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,234 @@
+use crate::consts;
+use crate::game_ui::focus_navigation::BackButton;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::*;
+use crate::localization::localized_text::LocalizedText;
+use crate::profile::profiles::*;
+use crate::GameState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub enum PlayerSelectionButtons {
+    Profile(usize),
+    Create,
+    Back,
+}
+
+/// Holds the buttons for the saved profiles.
+#[derive(Component)]
+pub struct ProfileList;
+
+/// Name being typed for a new profile.
+#[derive(Component, Default)]
+pub struct NameEntry(pub String);
+
+#[derive(Component)]
+pub struct OnPlayerSelectionScreen;
+
+fn spawn_profile_buttons(parent: &mut ChildBuilder, theme: &UiTheme, profiles: &Profiles) {
+    for (index, name) in profiles.names.iter().enumerate() {
+        // Profile names aren't table keys, so they show as written
+        spawn_text_button(
+            parent,
+            theme,
+            name,
+            200.0,
+            PlayerSelectionButtons::Profile(index),
+        );
+    }
+}
+
+pub fn setup_player_selection(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    profiles: Res<Profiles>,
+) {
+    commands
+        .spawn((
+            Node {
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(15.0),
+                padding: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+            panel(&theme),
+            OnPlayerSelectionScreen,
+        ))
+        .with_children(|parent| {
+            spawn_banner(parent, &theme, "profiles.title");
+            parent
+                .spawn((
+                    Node {
+                        max_width: Val::Px(650.0),
+                        flex_direction: FlexDirection::Row,
+                        flex_wrap: FlexWrap::Wrap,
+                        justify_content: JustifyContent::Center,
+                        row_gap: Val::Px(10.0),
+                        column_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    ProfileList,
+                ))
+                .with_children(|parent| spawn_profile_buttons(parent, &theme, &profiles));
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("profiles.new"),
+                theme.text(24.0),
+            ));
+            // New profile row: [typed name] [Create]
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(15.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            Node {
+                                width: Val::Px(320.0),
+                                height: Val::Px(BUTTON_HEIGHT),
+                                padding: UiRect::horizontal(Val::Px(12.0)),
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(consts::INPUT_FIELD_BG),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new("_"),
+                                theme.button_text(26.0),
+                                NameEntry::default(),
+                            ));
+                        });
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "profiles.create",
+                        150.0,
+                        PlayerSelectionButtons::Create,
+                    );
+                });
+            spawn_text_button(
+                parent,
+                &theme,
+                "menu.back",
+                250.0,
+                (PlayerSelectionButtons::Back, BackButton),
+            );
+        });
+}
+
+/// Types letters, digits and spaces into the new profile name; Backspace deletes.
+pub fn type_profile_name(
+    mut key_events: EventReader<KeyboardInput>,
+    mut entries: Query<(&mut NameEntry, &mut Text)>,
+) {
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        for (mut entry, mut text) in entries.iter_mut() {
+            match &event.logical_key {
+                Key::Backspace => {
+                    entry.0.pop();
+                }
+                Key::Space if entry.0.chars().count() < MAX_PROFILE_NAME => entry.0.push(' '),
+                Key::Character(typed) => {
+                    for character in typed.chars() {
+                        if character.is_alphanumeric() && entry.0.chars().count() < MAX_PROFILE_NAME
+                        {
+                            entry.0.push(character);
+                        }
+                    }
+                }
+                _ => continue,
+            }
+            // Trailing underscore as the cursor
+            text.0 = format!("{}_", entry.0);
+        }
+    }
+}
+
+pub fn player_selection_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &PlayerSelectionButtons),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut profiles: ResMut<Profiles>,
+    mut entries: Query<(&mut NameEntry, &mut Text)>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut ui_events: EventWriter<UiEvent>,
+) {
+    for (interaction, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
+                match button {
+                    PlayerSelectionButtons::Profile(index) => profiles.current = *index,
+                    PlayerSelectionButtons::Create => {
+                        for (mut entry, mut text) in entries.iter_mut() {
+                            if profiles.add(&entry.0) {
+                                entry.0.clear();
+                                text.0 = "_".to_string();
+                            }
+                        }
+                    }
+                    PlayerSelectionButtons::Back => game_state.set(GameState::MainMenu),
+                }
+            }
+            Interaction::Hovered => {
+                ui_events.send(UiEvent::ButtonHovered);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Rebuilds the profile buttons after a profile was added.
+pub fn refresh_profile_list(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    profiles: Res<Profiles>,
+    lists: Query<(Entity, &Children), With<ProfileList>>,
+) {
+    for (list, children) in lists.iter() {
+        if children.len() == profiles.names.len() {
+            continue;
+        }
+        commands
+            .entity(list)
+            .despawn_descendants()
+            .with_children(|parent| spawn_profile_buttons(parent, &theme, &profiles));
+    }
+}
+
+pub fn highlight_current_profile(
+    profiles: Res<Profiles>,
+    mut buttons: Query<(&PlayerSelectionButtons, &mut ThemedButton)>,
+) {
+    for (button, mut themed) in buttons.iter_mut() {
+        if let PlayerSelectionButtons::Profile(index) = button {
+            let selected = *index == profiles.current;
+            if themed.selected != selected {
+                themed.selected = selected;
+            }
+        }
+    }
+}
+
+pub fn cleanup_player_selection(
+    mut commands: Commands,
+    query: Query<Entity, With<OnPlayerSelectionScreen>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
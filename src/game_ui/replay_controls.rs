@@ -0,0 +1,138 @@
+use crate::engine::simulation::SimulationTick;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::{panel, spawn_text_button};
+use crate::localization::localized_text::LocalizedText;
+use crate::replay::playback::{Playback, PlaybackCommand};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub enum ReplayButtons {
+    ScrubBack,
+    PlayPause,
+    ScrubForward,
+}
+
+#[derive(Component)]
+pub struct ReplayTickLabel;
+
+#[derive(Component)]
+pub struct OnReplayControls;
+
+/// Bar along the bottom of the screen shown while watching a replay.
+pub fn setup_replay_controls(mut commands: Commands, theme: Res<UiTheme>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnReplayControls,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(15.0),
+                        padding: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+                        ..default()
+                    },
+                    panel(&theme),
+                ))
+                .with_children(|parent| {
+                    spawn_text_button(parent, &theme, "<<", 80.0, ReplayButtons::ScrubBack);
+                    spawn_text_button(parent, &theme, "||", 80.0, ReplayButtons::PlayPause);
+                    spawn_text_button(parent, &theme, ">>", 80.0, ReplayButtons::ScrubForward);
+                    parent.spawn((
+                        Text::default(),
+                        theme.text(24.0),
+                        Node {
+                            width: Val::Px(220.0),
+                            ..default()
+                        },
+                        ReplayTickLabel,
+                    ));
+                });
+        });
+}
+
+pub fn replay_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &ReplayButtons),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut playback_commands: EventWriter<PlaybackCommand>,
+    mut ui_events: EventWriter<UiEvent>,
+) {
+    for (interaction, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
+                playback_commands.send(match button {
+                    ReplayButtons::ScrubBack => PlaybackCommand::ScrubBack,
+                    ReplayButtons::PlayPause => PlaybackCommand::TogglePause,
+                    ReplayButtons::ScrubForward => PlaybackCommand::ScrubForward,
+                });
+            }
+            Interaction::Hovered => {
+                ui_events.send(UiEvent::ButtonHovered);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Space toggles play/pause, the arrow keys scrub.
+pub fn replay_keyboard_controls(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut playback_commands: EventWriter<PlaybackCommand>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        playback_commands.send(PlaybackCommand::TogglePause);
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+        playback_commands.send(PlaybackCommand::ScrubBack);
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+        playback_commands.send(PlaybackCommand::ScrubForward);
+    }
+}
+
+pub fn update_replay_controls(
+    playback: Res<Playback>,
+    tick: Res<SimulationTick>,
+    mut labels: Query<&mut Text, With<ReplayTickLabel>>,
+    buttons: Query<(&ReplayButtons, &Children)>,
+    mut button_texts: Query<&mut LocalizedText>,
+) {
+    for mut text in labels.iter_mut() {
+        text.0 = format!("{} / {}", tick.0, playback.replay.last_tick());
+    }
+    for (button, children) in buttons.iter() {
+        if !matches!(button, ReplayButtons::PlayPause) {
+            continue;
+        }
+        for child in children.iter() {
+            if let Ok(mut text) = button_texts.get_mut(*child) {
+                let symbol = if playback.paused { ">" } else { "||" };
+                if text.0 != symbol {
+                    text.0 = symbol.to_string();
+                }
+            }
+        }
+    }
+}
+
+pub fn cleanup_replay_controls(
+    mut commands: Commands,
+    query: Query<Entity, With<OnReplayControls>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
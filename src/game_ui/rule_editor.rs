@@ -0,0 +1,432 @@
+use crate::data::rule_set::*;
+use crate::data::tuning::Tuning;
+use crate::game_ui::focus_navigation::BackButton;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::*;
+use crate::localization::localized_text::LocalizedText;
+use crate::localization::string_table::StringTable;
+use crate::GameState;
+use bevy::prelude::*;
+
+/// Longest name that can be typed for a rule set, in characters.
+pub const MAX_RULE_SET_NAME: usize = 20;
+/// Width of the "-" and "+" buttons.
+const ADJUST_WIDTH: f32 = 55.0;
+
+/// One editable number of a `RuleSet`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleField {
+    PlayerGravity,
+    WorldGravity,
+    WalkSpeed,
+    RunMultiplier,
+    JumpVelocity,
+    SpawnRate,
+    ExplosionBase,
+    ExplosionMax,
+    Hp,
+    Damage,
+}
+
+impl RuleField {
+    pub const ALL: [RuleField; 10] = [
+        RuleField::PlayerGravity,
+        RuleField::WorldGravity,
+        RuleField::WalkSpeed,
+        RuleField::RunMultiplier,
+        RuleField::JumpVelocity,
+        RuleField::SpawnRate,
+        RuleField::ExplosionBase,
+        RuleField::ExplosionMax,
+        RuleField::Hp,
+        RuleField::Damage,
+    ];
+
+    /// String table key of the row label, which takes the value as `{value}`.
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            RuleField::PlayerGravity => "rules.player_gravity",
+            RuleField::WorldGravity => "rules.world_gravity",
+            RuleField::WalkSpeed => "rules.walk_speed",
+            RuleField::RunMultiplier => "rules.run_multiplier",
+            RuleField::JumpVelocity => "rules.jump_velocity",
+            RuleField::SpawnRate => "rules.spawn_rate",
+            RuleField::ExplosionBase => "rules.explosion_base",
+            RuleField::ExplosionMax => "rules.explosion_max",
+            RuleField::Hp => "rules.hp",
+            RuleField::Damage => "rules.damage",
+        }
+    }
+
+    pub fn value_text(&self, rules: &RuleSet) -> String {
+        match self {
+            // Gravity pulls down, so it is stored negative but shown as a strength
+            RuleField::PlayerGravity => format!("{:.0}", -rules.player_gravity),
+            RuleField::WorldGravity => format!("{:.0}", -rules.world_gravity),
+            RuleField::WalkSpeed => format!("{:.0}", rules.walk_speed),
+            RuleField::RunMultiplier => format!("x{:.2}", rules.run_multiplier),
+            RuleField::JumpVelocity => format!("{:.0}", rules.jump_velocity),
+            RuleField::SpawnRate => format!("x{:.2}", rules.spawn_rate),
+            RuleField::ExplosionBase => format!("{:.0}%", rules.explosion_base * 100.0),
+            RuleField::ExplosionMax => format!("{:.0}%", rules.explosion_max * 100.0),
+            RuleField::Hp => rules.hp.to_string(),
+            RuleField::Damage => rules.damage.to_string(),
+        }
+    }
+
+    /// Moves the value one step up (`up`) or down, within the range the game plays well in.
+    pub fn adjust(&self, rules: &mut RuleSet, up: bool) {
+        let sign = if up { 1.0 } else { -1.0 };
+        let step = |value: f32, by: f32, min: f32, max: f32| (value + sign * by).clamp(min, max);
+        match self {
+            RuleField::PlayerGravity => {
+                rules.player_gravity = step(rules.player_gravity, -50.0, -3000.0, -100.0);
+            }
+            RuleField::WorldGravity => {
+                rules.world_gravity = step(rules.world_gravity, -25.0, -1500.0, -25.0);
+            }
+            RuleField::WalkSpeed => rules.walk_speed = step(rules.walk_speed, 8.0, 8.0, 256.0),
+            RuleField::RunMultiplier => {
+                rules.run_multiplier = step(rules.run_multiplier, 0.25, 1.0, 4.0);
+            }
+            RuleField::JumpVelocity => {
+                rules.jump_velocity = step(rules.jump_velocity, 8.0, 16.0, 256.0);
+            }
+            RuleField::SpawnRate => rules.spawn_rate = step(rules.spawn_rate, 0.25, 0.25, 4.0),
+            // Whole percentages, with the first crate's odds never above the cap
+            RuleField::ExplosionBase => {
+                rules.explosion_base = (step(
+                    rules.explosion_base,
+                    0.01,
+                    0.0,
+                    rules.explosion_max.max(0.0),
+                ) * 100.0)
+                    .round()
+                    / 100.0;
+            }
+            RuleField::ExplosionMax => {
+                rules.explosion_max = (step(
+                    rules.explosion_max,
+                    0.05,
+                    rules.explosion_base.min(1.0),
+                    1.0,
+                ) * 100.0)
+                    .round()
+                    / 100.0;
+            }
+            RuleField::Hp => rules.hp = step(rules.hp as f32, 10.0, 10.0, 500.0) as u32,
+            RuleField::Damage => rules.damage = step(rules.damage as f32, 5.0, 0.0, 200.0) as u32,
+        }
+    }
+}
+
+/// Rule set being edited, and the saved ones that can be loaded into it.
+#[derive(Resource)]
+pub struct RuleDraft {
+    pub rules: RuleSet,
+    pub saved: Vec<RuleSet>,
+}
+
+#[derive(Component)]
+pub enum RuleEditorButtons {
+    Adjust(RuleField, bool),
+    Load(usize),
+    Save,
+    /// Plays the next rounds with the draft.
+    Use,
+    Back,
+}
+
+/// Label of a rule row, showing its current value.
+#[derive(Component)]
+pub struct RuleValue(pub RuleField);
+
+#[derive(Component)]
+pub struct RuleSetName;
+
+/// Holds the buttons for the saved rule sets.
+#[derive(Component)]
+pub struct SavedRuleSetList;
+
+/// Result of the last save.
+#[derive(Component)]
+pub struct RuleEditorStatus;
+
+#[derive(Component)]
+pub struct OnRuleEditorScreen;
+
+fn spawn_saved_buttons(parent: &mut ChildBuilder, theme: &UiTheme, saved: &[RuleSet]) {
+    for (index, rules) in saved.iter().enumerate() {
+        // Rule set names aren't table keys, so they show as written
+        spawn_text_button(
+            parent,
+            theme,
+            &rules.name,
+            200.0,
+            RuleEditorButtons::Load(index),
+        );
+    }
+}
+
+fn spawn_rule_row(parent: &mut ChildBuilder, theme: &UiTheme, field: RuleField) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            spawn_text_button(
+                parent,
+                theme,
+                "rules.less",
+                ADJUST_WIDTH,
+                RuleEditorButtons::Adjust(field, false),
+            );
+            spawn_text_button(
+                parent,
+                theme,
+                "rules.more",
+                ADJUST_WIDTH,
+                RuleEditorButtons::Adjust(field, true),
+            );
+            parent.spawn((
+                Text::default(),
+                theme.text(22.0),
+                Node {
+                    width: Val::Px(300.0),
+                    ..default()
+                },
+                RuleValue(field),
+            ));
+        });
+}
+
+/// Starts editing from the rules in play, so picking a preset first and tweaking it works.
+pub fn setup_rule_editor(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    tuning: Res<Tuning>,
+    selected: Res<SelectedRules>,
+) {
+    let mut rules = RuleSet::from_tuning(&tuning);
+    if let RuleChoice::Custom(custom) = &selected.0 {
+        rules.name = custom.name.clone();
+    }
+    let saved = RuleSet::load_all();
+    commands
+        .spawn((
+            Node {
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+            panel(&theme),
+            OnRuleEditorScreen,
+        ))
+        .with_children(|parent| {
+            spawn_banner(parent, &theme, "rules.title");
+            // Name row: [typed name] [Save]
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(15.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("rules.name"),
+                        theme.text(24.0),
+                    ));
+                    let mut field = TextField::new(MAX_RULE_SET_NAME);
+                    field.text = rules.name.clone();
+                    spawn_text_field(parent, &theme, field, 320.0, RuleSetName);
+                    spawn_text_button(parent, &theme, "rules.save", 150.0, RuleEditorButtons::Save);
+                });
+            // Two columns of rows
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(30.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for column in RuleField::ALL.chunks(5) {
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(6.0),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                for field in column {
+                                    spawn_rule_row(parent, &theme, *field);
+                                }
+                            });
+                    }
+                });
+            // Saved row: "Saved:" [name] [name] ...
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(15.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("rules.saved"),
+                        theme.text(22.0),
+                    ));
+                    parent
+                        .spawn((
+                            Node {
+                                max_width: Val::Px(840.0),
+                                flex_direction: FlexDirection::Row,
+                                flex_wrap: FlexWrap::Wrap,
+                                row_gap: Val::Px(8.0),
+                                column_gap: Val::Px(8.0),
+                                ..default()
+                            },
+                            SavedRuleSetList,
+                        ))
+                        .with_children(|parent| spawn_saved_buttons(parent, &theme, &saved));
+                });
+            parent.spawn((Text::default(), theme.text(18.0), RuleEditorStatus));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_text_button(
+                        parent,
+                        &theme,
+                        "menu.back",
+                        250.0,
+                        (RuleEditorButtons::Back, BackButton),
+                    );
+                    spawn_text_button(parent, &theme, "rules.use", 250.0, RuleEditorButtons::Use);
+                });
+        });
+    commands.insert_resource(RuleDraft { rules, saved });
+}
+
+pub fn rule_editor_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &RuleEditorButtons),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut draft: ResMut<RuleDraft>,
+    mut names: Query<&mut TextField, With<RuleSetName>>,
+    mut statuses: Query<&mut Text, With<RuleEditorStatus>>,
+    strings: Res<StringTable>,
+    mut selected: ResMut<SelectedRules>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut ui_events: EventWriter<UiEvent>,
+) {
+    for (interaction, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
+                // The typed name belongs to whatever is saved or used next
+                if let Some(name) = names.iter().next() {
+                    draft.rules.name = name.text.trim().to_string();
+                }
+                match button {
+                    RuleEditorButtons::Adjust(field, up) => field.adjust(&mut draft.rules, *up),
+                    RuleEditorButtons::Load(index) => {
+                        if let Some(rules) = draft.saved.get(*index).cloned() {
+                            for mut name in names.iter_mut() {
+                                name.text = rules.name.clone();
+                            }
+                            draft.rules = rules;
+                        }
+                    }
+                    RuleEditorButtons::Save => {
+                        let status = if draft.rules.name.is_empty() {
+                            strings.get("rules.need_name").to_string()
+                        } else {
+                            match draft.rules.save() {
+                                Ok(path) => {
+                                    draft.saved = RuleSet::load_all();
+                                    strings.format(
+                                        "rules.saved_to",
+                                        &[("path", path.display().to_string())],
+                                    )
+                                }
+                                Err(error) => {
+                                    warn!("{error}");
+                                    strings.format(
+                                        "rules.save_failed",
+                                        &[("error", error.to_string())],
+                                    )
+                                }
+                            }
+                        };
+                        for mut text in statuses.iter_mut() {
+                            text.0 = status.clone();
+                        }
+                    }
+                    RuleEditorButtons::Use => {
+                        selected.0 = RuleChoice::Custom(draft.rules.clone());
+                        game_state.set(GameState::ModeSelect);
+                    }
+                    RuleEditorButtons::Back => game_state.set(GameState::ModeSelect),
+                }
+            }
+            Interaction::Hovered => {
+                ui_events.send(UiEvent::ButtonHovered);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+pub fn update_rule_values(
+    draft: Res<RuleDraft>,
+    strings: Res<StringTable>,
+    mut labels: Query<(&mut Text, &RuleValue)>,
+) {
+    for (mut text, value) in labels.iter_mut() {
+        text.0 = strings.format(
+            value.0.label_key(),
+            &[("value", value.0.value_text(&draft.rules))],
+        );
+    }
+}
+
+/// Rebuilds the saved rule set buttons after a save.
+pub fn refresh_saved_rule_sets(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    draft: Res<RuleDraft>,
+    lists: Query<(Entity, Option<&Children>), With<SavedRuleSetList>>,
+) {
+    for (list, children) in lists.iter() {
+        if children.map_or(0, |children| children.len()) == draft.saved.len() {
+            continue;
+        }
+        commands
+            .entity(list)
+            .despawn_descendants()
+            .with_children(|parent| spawn_saved_buttons(parent, &theme, &draft.saved));
+    }
+}
+
+pub fn cleanup_rule_editor(mut commands: Commands, query: Query<Entity, With<OnRuleEditorScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<RuleDraft>();
+}
@@ -0,0 +1,56 @@
+use crate::consts;
+use crate::engine::assist::AssistSettings;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+/// Seconds a new screen takes to fade in from black.
+pub const FADE_SECS: f32 = 0.35;
+
+/// Black overlay that fades out over the screen that was just opened.
+#[derive(Component)]
+pub struct ScreenFade(Timer);
+
+/// Covers the screen in black when the game state changes, so the next screen fades in
+/// instead of popping up. Skipped with reduced motion on.
+pub fn start_screen_fade(
+    mut commands: Commands,
+    assist: Res<AssistSettings>,
+    fades: Query<Entity, With<ScreenFade>>,
+) {
+    for entity in fades.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if assist.reduced_motion {
+        return;
+    }
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(consts::BLACK),
+        // Above every screen, but clicks go through to the buttons underneath
+        GlobalZIndex(i32::MAX),
+        FocusPolicy::Pass,
+        PickingBehavior::IGNORE,
+        ScreenFade(Timer::from_seconds(FADE_SECS, TimerMode::Once)),
+    ));
+}
+
+/// Runs on real time so the fade also finishes while the game is paused.
+pub fn update_screen_fade(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut fades: Query<(Entity, &mut ScreenFade, &mut BackgroundColor)>,
+) {
+    for (entity, mut fade, mut background) in fades.iter_mut() {
+        fade.0.tick(time.delta());
+        if fade.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            background.0.set_alpha(fade.0.fraction_remaining());
+        }
+    }
+}
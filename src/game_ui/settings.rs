@@ -1 +1,422 @@
+use crate::accessibility::palette::PaletteSettings;
+use crate::audio::volume::*;
+use crate::engine::assist::*;
+use crate::game_ui::focus_navigation::BackButton;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::*;
+use crate::localization::language::*;
+use crate::localization::string_table::StringTable;
+use crate::tutor::tutor_settings::*;
+use crate::{GameState, PauseMenu};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
 
+/// Amount a volume bus changes per button press.
+const VOLUME_STEP: f32 = 0.1;
+
+#[derive(Component)]
+pub enum SettingsButtons {
+    VolumeDown(VolumeBus),
+    VolumeUp(VolumeBus),
+    ToggleTutor,
+    CycleAgeGroup,
+    CycleLanguage,
+    CyclePalette,
+    ToggleLayerSymbols,
+    CycleGameSpeed,
+    ToggleRunMode,
+    ToggleAutoAttack,
+    ToggleReducedMotion,
+    CycleUiScale,
+    CycleExtraHp,
+    Back,
+}
+
+#[derive(Component)]
+pub struct VolumeLabel(pub VolumeBus);
+
+#[derive(Component)]
+pub struct VolumeSlider(pub VolumeBus);
+
+/// Label of a "text [button]" or "text [checkbox]" settings row.
+#[derive(Component)]
+pub enum OptionLabel {
+    Tutor,
+    AgeGroup,
+    Language,
+    Palette,
+    LayerSymbols,
+    GameSpeed,
+    RunMode,
+    AutoAttack,
+    ReducedMotion,
+    UiScale,
+    ExtraHp,
+}
+
+#[derive(Component)]
+pub struct OnSettingsScreen;
+
+/// Settings shown in the option rows.
+#[derive(SystemParam)]
+pub struct OptionValues<'w> {
+    tutor: Res<'w, TutorSettings>,
+    palette: Res<'w, PaletteSettings>,
+    assist: Res<'w, AssistSettings>,
+    catalog: Res<'w, LanguageCatalog>,
+    language: Res<'w, CurrentLanguage>,
+}
+
+impl OptionValues<'_> {
+    fn text(&self, label: &OptionLabel, strings: &StringTable) -> String {
+        let percent = |value: f32| ("percent", format!("{:.0}", value * 100.0));
+        match label {
+            OptionLabel::Tutor => strings.get("settings.tutor").to_string(),
+            OptionLabel::AgeGroup => strings.format(
+                "settings.age_group",
+                &[(
+                    "group",
+                    strings.get(self.tutor.age_group.label_key()).to_string(),
+                )],
+            ),
+            OptionLabel::Language => {
+                let name = self
+                    .catalog
+                    .0
+                    .get(self.language.0)
+                    .map_or("", |entry| entry.name);
+                strings.format("settings.language", &[("language", name.to_string())])
+            }
+            OptionLabel::Palette => strings.format(
+                "settings.palette",
+                &[(
+                    "palette",
+                    strings.get(self.palette.preset.label_key()).to_string(),
+                )],
+            ),
+            OptionLabel::LayerSymbols => strings.get("settings.layer_symbols").to_string(),
+            OptionLabel::GameSpeed => {
+                strings.format("settings.game_speed", &[percent(self.assist.game_speed)])
+            }
+            OptionLabel::RunMode => strings.format(
+                "settings.run_mode",
+                &[(
+                    "mode",
+                    strings
+                        .get(if self.assist.toggle_run {
+                            "settings.run_mode.toggle"
+                        } else {
+                            "settings.run_mode.hold"
+                        })
+                        .to_string(),
+                )],
+            ),
+            OptionLabel::AutoAttack => strings.get("settings.auto_attack").to_string(),
+            OptionLabel::ReducedMotion => strings.get("settings.reduced_motion").to_string(),
+            OptionLabel::UiScale => {
+                strings.format("settings.ui_scale", &[percent(self.assist.ui_scale)])
+            }
+            OptionLabel::ExtraHp => strings.format(
+                "settings.extra_hp",
+                &[("hp", self.assist.extra_hp.to_string())],
+            ),
+        }
+    }
+
+    /// State of the checkbox for on/off settings, `None` for settings with a button.
+    fn checked(&self, button: &SettingsButtons) -> Option<bool> {
+        match button {
+            SettingsButtons::ToggleTutor => Some(self.tutor.enabled),
+            SettingsButtons::ToggleLayerSymbols => Some(self.palette.layer_symbols),
+            SettingsButtons::ToggleAutoAttack => Some(self.assist.auto_attack),
+            SettingsButtons::ToggleReducedMotion => Some(self.assist.reduced_motion),
+            _ => None,
+        }
+    }
+}
+
+fn volume_text(bus: VolumeBus, volume: &VolumeSettings, strings: &StringTable) -> String {
+    strings.format(
+        "settings.volume",
+        &[
+            ("bus", strings.get(bus.label_key()).to_string()),
+            ("percent", format!("{:.0}", volume.get(bus) * 100.0)),
+        ],
+    )
+}
+
+fn spawn_column(parent: &mut ChildBuilder, build: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(15.0),
+            ..default()
+        })
+        .with_children(build);
+}
+
+/// Option rows: label [button], or label [checkbox] for settings that are on or off
+fn spawn_option_rows(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    rows: impl IntoIterator<Item = (OptionLabel, SettingsButtons)>,
+    values: &OptionValues,
+    strings: &StringTable,
+) {
+    for (label, button) in rows {
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(15.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(values.text(&label, strings)),
+                    theme.text(24.0),
+                    Node {
+                        width: Val::Px(360.0),
+                        ..default()
+                    },
+                    label,
+                ));
+                match values.checked(&button) {
+                    Some(checked) => spawn_checkbox(parent, theme, checked, button),
+                    None => spawn_text_button(parent, theme, "settings.change", 150.0, button),
+                }
+            });
+    }
+}
+
+pub fn setup_settings_ui(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    volume: Res<VolumeSettings>,
+    values: OptionValues,
+    strings: Res<StringTable>,
+) {
+    commands
+        .spawn((
+            Node {
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(15.0),
+                padding: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+            panel(&theme),
+            OnSettingsScreen,
+        ))
+        .with_children(|parent| {
+            spawn_banner(parent, &theme, "menu.settings");
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::FlexStart,
+                    column_gap: Val::Px(40.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    // General settings on the left, assists on the right
+                    spawn_column(parent, |parent| {
+                        // One row per volume bus: label [-] slider [+]
+                        for bus in VolumeBus::ALL {
+                            parent
+                                .spawn(Node {
+                                    flex_direction: FlexDirection::Row,
+                                    align_items: AlignItems::Center,
+                                    column_gap: Val::Px(15.0),
+                                    ..default()
+                                })
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Text::new(volume_text(bus, &volume, &strings)),
+                                        theme.text(24.0),
+                                        Node {
+                                            width: Val::Px(200.0),
+                                            ..default()
+                                        },
+                                        VolumeLabel(bus),
+                                    ));
+                                    spawn_text_button(
+                                        parent,
+                                        &theme,
+                                        "-",
+                                        55.0,
+                                        SettingsButtons::VolumeDown(bus),
+                                    );
+                                    spawn_slider(
+                                        parent,
+                                        &theme,
+                                        volume.get(bus),
+                                        180.0,
+                                        VolumeSlider(bus),
+                                    );
+                                    spawn_text_button(
+                                        parent,
+                                        &theme,
+                                        "+",
+                                        55.0,
+                                        SettingsButtons::VolumeUp(bus),
+                                    );
+                                });
+                        }
+                        spawn_option_rows(
+                            parent,
+                            &theme,
+                            [
+                                (OptionLabel::Language, SettingsButtons::CycleLanguage),
+                                (OptionLabel::Tutor, SettingsButtons::ToggleTutor),
+                                (OptionLabel::AgeGroup, SettingsButtons::CycleAgeGroup),
+                                (OptionLabel::Palette, SettingsButtons::CyclePalette),
+                                (
+                                    OptionLabel::LayerSymbols,
+                                    SettingsButtons::ToggleLayerSymbols,
+                                ),
+                            ],
+                            &values,
+                            &strings,
+                        );
+                    });
+                    spawn_column(parent, |parent| {
+                        spawn_option_rows(
+                            parent,
+                            &theme,
+                            [
+                                (OptionLabel::GameSpeed, SettingsButtons::CycleGameSpeed),
+                                (OptionLabel::RunMode, SettingsButtons::ToggleRunMode),
+                                (OptionLabel::AutoAttack, SettingsButtons::ToggleAutoAttack),
+                                (
+                                    OptionLabel::ReducedMotion,
+                                    SettingsButtons::ToggleReducedMotion,
+                                ),
+                                (OptionLabel::UiScale, SettingsButtons::CycleUiScale),
+                                (OptionLabel::ExtraHp, SettingsButtons::CycleExtraHp),
+                            ],
+                            &values,
+                            &strings,
+                        );
+                    });
+                });
+            spawn_text_button(
+                parent,
+                &theme,
+                "menu.back",
+                250.0,
+                (SettingsButtons::Back, BackButton),
+            );
+        });
+}
+
+pub fn settings_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &SettingsButtons),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut volume: ResMut<VolumeSettings>,
+    mut tutor: ResMut<TutorSettings>,
+    mut palette: ResMut<PaletteSettings>,
+    mut assist: ResMut<AssistSettings>,
+    catalog: Res<LanguageCatalog>,
+    mut language: ResMut<CurrentLanguage>,
+    current_state: Res<State<GameState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut pause_menu: ResMut<NextState<PauseMenu>>,
+    mut ui_events: EventWriter<UiEvent>,
+) {
+    for (interaction, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
+                match button {
+                    SettingsButtons::VolumeDown(bus) => volume.adjust(*bus, -VOLUME_STEP),
+                    SettingsButtons::VolumeUp(bus) => volume.adjust(*bus, VOLUME_STEP),
+                    SettingsButtons::ToggleTutor => tutor.enabled = !tutor.enabled,
+                    SettingsButtons::CycleAgeGroup => tutor.age_group = tutor.age_group.next(),
+                    SettingsButtons::CycleLanguage => *language = language.next(&catalog),
+                    SettingsButtons::CyclePalette => palette.preset = palette.preset.next(),
+                    SettingsButtons::ToggleLayerSymbols => {
+                        palette.layer_symbols = !palette.layer_symbols
+                    }
+                    SettingsButtons::CycleGameSpeed => {
+                        assist.game_speed = next_step(&GAME_SPEEDS, assist.game_speed)
+                    }
+                    SettingsButtons::ToggleRunMode => assist.toggle_run = !assist.toggle_run,
+                    SettingsButtons::ToggleAutoAttack => assist.auto_attack = !assist.auto_attack,
+                    SettingsButtons::ToggleReducedMotion => {
+                        assist.reduced_motion = !assist.reduced_motion
+                    }
+                    SettingsButtons::CycleUiScale => {
+                        assist.ui_scale = next_step(&UI_SCALES, assist.ui_scale)
+                    }
+                    SettingsButtons::CycleExtraHp => {
+                        assist.extra_hp = next_step(&EXTRA_HP_STEPS, assist.extra_hp)
+                    }
+                    // Settings opened from the pause menu return to it, keeping the round
+                    SettingsButtons::Back => match current_state.get() {
+                        GameState::InGame => pause_menu.set(PauseMenu::Main),
+                        _ => game_state.set(GameState::MainMenu),
+                    },
+                }
+            }
+            Interaction::Hovered => {
+                ui_events.send(UiEvent::ButtonHovered);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Sets a bus to its slider's value when the slider is dragged.
+pub fn apply_volume_sliders(
+    mut volume: ResMut<VolumeSettings>,
+    sliders: Query<(&Slider, &VolumeSlider), Changed<Slider>>,
+) {
+    for (slider, bus) in sliders.iter() {
+        // Leave the resource unchanged when the slider was only synced to it
+        if volume.get(bus.0) != slider.0 {
+            volume.set(bus.0, slider.0);
+        }
+    }
+}
+
+pub fn update_volume_labels(
+    volume: Res<VolumeSettings>,
+    strings: Res<StringTable>,
+    mut labels: Query<(&mut Text, &VolumeLabel)>,
+    mut sliders: Query<(&mut Slider, &VolumeSlider)>,
+) {
+    for (mut text, label) in labels.iter_mut() {
+        text.0 = volume_text(label.0, &volume, &strings);
+    }
+    for (mut slider, bus) in sliders.iter_mut() {
+        slider.set_if_neq(Slider(volume.get(bus.0)));
+    }
+}
+
+pub fn update_option_labels(
+    values: OptionValues,
+    strings: Res<StringTable>,
+    mut labels: Query<(&mut Text, &OptionLabel)>,
+    mut checkboxes: Query<(&mut Checkbox, &SettingsButtons)>,
+) {
+    for (mut text, label) in labels.iter_mut() {
+        text.0 = values.text(label, &strings);
+    }
+    for (mut checkbox, button) in checkboxes.iter_mut() {
+        if let Some(checked) = values.checked(button) {
+            checkbox.set_if_neq(Checkbox(checked));
+        }
+    }
+}
+
+pub fn cleanup_settings(mut commands: Commands, query: Query<Entity, With<OnSettingsScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,260 @@
+use crate::consts;
+use crate::game_ui::focus_navigation::BackButton;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::{panel, spawn_banner, spawn_text_button};
+use crate::localization::string_table::StringTable;
+use crate::telemetry::session_stats::*;
+use crate::GameState;
+use bevy::prelude::*;
+
+/// Height of the tallest bar in the charts.
+const CHART_HEIGHT: f32 = 140.0;
+/// Most recent sessions listed under the trends heading.
+const TREND_SESSIONS: usize = 6;
+
+#[derive(Component)]
+pub enum StatsButtons {
+    Back,
+}
+
+#[derive(Component)]
+pub struct OnStatsScreen;
+
+fn spawn_label(parent: &mut ChildBuilder, text: impl Into<String>, theme: &UiTheme, size: f32) {
+    parent.spawn((Text::new(text), theme.text(size)));
+}
+
+fn spawn_bar(parent: &mut ChildBuilder, fraction: f32, width: f32, color: Color) {
+    parent.spawn((
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(CHART_HEIGHT * fraction.clamp(0.0, 1.0)),
+            ..default()
+        },
+        BackgroundColor(color),
+    ));
+}
+
+/// Row of bar groups sitting on a common baseline.
+fn spawn_chart(parent: &mut ChildBuilder, build: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::FlexEnd,
+            column_gap: Val::Px(8.0),
+            height: Val::Px(CHART_HEIGHT + 24.0),
+            ..default()
+        })
+        .with_children(build);
+}
+
+/// Bars stacked above a caption, e.g. one calibration bin or histogram bucket.
+fn spawn_bar_group(
+    parent: &mut ChildBuilder,
+    caption: String,
+    theme: &UiTheme,
+    build: impl FnOnce(&mut ChildBuilder),
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::FlexEnd,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::FlexEnd,
+                    column_gap: Val::Px(2.0),
+                    ..default()
+                })
+                .with_children(build);
+            spawn_label(parent, caption, theme, 14.0);
+        });
+}
+
+pub fn setup_stats_screen(mut commands: Commands, theme: Res<UiTheme>, strings: Res<StringTable>) {
+    let stats = ClassroomStats::load();
+    commands
+        .spawn((
+            Node {
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                padding: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+            panel(&theme),
+            OnStatsScreen,
+        ))
+        .with_children(|parent| {
+            spawn_banner(parent, &theme, "menu.stats");
+            if stats.is_empty() {
+                spawn_label(parent, strings.get("stats.empty"), &theme, 20.0);
+            } else {
+                spawn_calibration_chart(parent, &stats, &strings, &theme);
+                spawn_streak_histogram(parent, &stats, &strings, &theme);
+                spawn_session_trends(parent, &stats, &strings, &theme);
+            }
+            spawn_text_button(
+                parent,
+                &theme,
+                "menu.back",
+                250.0,
+                (StatsButtons::Back, BackButton),
+            );
+        });
+}
+
+/// Predicted chance next to how often crates in that range really exploded.
+fn spawn_calibration_chart(
+    parent: &mut ChildBuilder,
+    stats: &ClassroomStats,
+    strings: &StringTable,
+    theme: &UiTheme,
+) {
+    spawn_label(parent, strings.get("stats.calibration"), theme, 26.0);
+    spawn_chart(parent, |parent| {
+        for (index, bin) in stats.calibration.iter().enumerate() {
+            let low = index as f32 * CALIBRATION_BIN_WIDTH * 100.0;
+            let caption = format!("{low:.0}%+");
+            spawn_bar_group(parent, caption, theme, |parent| {
+                spawn_bar(
+                    parent,
+                    bin.predicted().unwrap_or(0.0),
+                    12.0,
+                    consts::PREDICTED_BAR,
+                );
+                spawn_bar(
+                    parent,
+                    bin.observed().unwrap_or(0.0),
+                    12.0,
+                    consts::OBSERVED_BAR,
+                );
+            });
+        }
+    });
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(20.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(strings.get("stats.predicted")),
+                TextFont {
+                    font: theme.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(consts::PREDICTED_BAR),
+            ));
+            parent.spawn((
+                Text::new(strings.get("stats.actual")),
+                TextFont {
+                    font: theme.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(consts::OBSERVED_BAR),
+            ));
+        });
+}
+
+fn spawn_streak_histogram(
+    parent: &mut ChildBuilder,
+    stats: &ClassroomStats,
+    strings: &StringTable,
+    theme: &UiTheme,
+) {
+    spawn_label(parent, strings.get("stats.streaks"), theme, 26.0);
+    let tallest = stats.streaks.iter().copied().max().unwrap_or(0).max(1) as f32;
+    spawn_chart(parent, |parent| {
+        for (count, rounds) in stats.streaks.iter().enumerate() {
+            let caption = if count == STREAK_BUCKETS - 1 {
+                format!("{count}+")
+            } else {
+                count.to_string()
+            };
+            spawn_bar_group(parent, caption, theme, |parent| {
+                spawn_bar(
+                    parent,
+                    *rounds as f32 / tallest,
+                    24.0,
+                    consts::HISTOGRAM_BAR,
+                );
+            });
+        }
+    });
+    spawn_label(
+        parent,
+        strings.format(
+            "stats.no_explosion",
+            &[("count", stats.rounds_without_explosion.to_string())],
+        ),
+        theme,
+        16.0,
+    );
+}
+
+fn spawn_session_trends(
+    parent: &mut ChildBuilder,
+    stats: &ClassroomStats,
+    strings: &StringTable,
+    theme: &UiTheme,
+) {
+    spawn_label(parent, strings.get("stats.sessions"), theme, 26.0);
+    let skip = stats.sessions.len().saturating_sub(TREND_SESSIONS);
+    for (number, trend) in stats.sessions.iter().enumerate().skip(skip) {
+        spawn_label(
+            parent,
+            strings.format(
+                "stats.session_line",
+                &[
+                    ("number", (number + 1).to_string()),
+                    ("rounds", trend.rounds.to_string()),
+                    ("score", format!("{:.1}", trend.mean_score)),
+                    ("percent", format!("{:.0}", trend.explosion_rate * 100.0)),
+                ],
+            ),
+            theme,
+            18.0,
+        );
+    }
+}
+
+pub fn stats_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &StatsButtons),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut ui_events: EventWriter<UiEvent>,
+) {
+    for (interaction, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                ui_events.send(UiEvent::ButtonPressed);
+                match button {
+                    StatsButtons::Back => game_state.set(GameState::MainMenu),
+                }
+            }
+            Interaction::Hovered => {
+                ui_events.send(UiEvent::ButtonHovered);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+pub fn cleanup_stats_screen(mut commands: Commands, query: Query<Entity, With<OnStatsScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
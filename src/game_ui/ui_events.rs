@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+/// Menu interactions other subsystems (such as audio) react to.
+#[derive(Event, Debug, Clone, Copy)]
+pub enum UiEvent {
+    ButtonHovered,
+    ButtonPressed,
+}
@@ -1,22 +1,203 @@
+use crate::accessibility::palette::PaletteSettings;
+use crate::audio::volume::VolumeSettings;
+use crate::engine::assist::AssistSettings;
+use crate::game_ui::credits::*;
+use crate::game_ui::focus_navigation::*;
+use crate::game_ui::game_over::*;
+use crate::game_ui::hud::*;
+use crate::game_ui::loading_screen::*;
 use crate::game_ui::main_menu::*;
+use crate::game_ui::mode_select::*;
+use crate::game_ui::pause_menu::*;
+use crate::game_ui::player_selection::*;
+use crate::game_ui::replay_controls::*;
+use crate::game_ui::rule_editor::*;
+use crate::game_ui::screen_transition::*;
 use crate::game_ui::settings::*;
-use crate::GameState;
+use crate::game_ui::stats_screen::*;
+use crate::game_ui::ui_events::UiEvent;
+use crate::game_ui::ui_theme::*;
+use crate::game_ui::widgets::*;
+use crate::localization::language::{CurrentLanguage, UiFont};
+use crate::localization::string_table::StringTable;
+use crate::profile::leaderboard::record_time_attack_score;
+use crate::profile::profiles::Profiles;
+use crate::replay::playback::Playback;
+use crate::tutor::tutor_settings::TutorSettings;
+use crate::{GameState, InGameState, PauseMenu};
 use bevy::prelude::*;
-use bevy_egui::EguiPlugin;
+use bevy::ui::UiSystem;
 
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::MainMenu), setup_main_menu)
+        app.add_event::<UiEvent>()
+            .init_resource::<FocusNavigation>()
+            .init_resource::<UiTheme>()
+            .add_systems(Startup, spawn_camera)
             .add_systems(
                 Update,
-                button_interaction_system.run_if(in_state(GameState::MainMenu)),
+                (
+                    sync_theme_font.run_if(resource_changed::<UiFont>),
+                    drag_sliders,
+                    update_bar_fills,
+                    update_button_visuals,
+                    update_checkbox_visuals,
+                    (type_into_text_fields, update_text_fields).chain(),
+                ),
             )
-            .add_systems(OnExit(GameState::MainMenu), cleanup_menu);
-        //.add_systems(OnEnter(GameState::Settings), setup_settings_ui)
-        //.add_systems(OnExit(GameState::Settings), cleanup_settings)
-        //.init_resource::<SettingsState>()
-        //.add_plugins(EguiPlugin);
+            // Arrow keys and Enter drive the player during a round
+            .add_systems(
+                PreUpdate,
+                (
+                    release_activated_button,
+                    activate_focused.run_if(not(in_state(InGameState::Running))),
+                )
+                    .chain()
+                    .after(UiSystem::Focus),
+            )
+            .add_systems(
+                Update,
+                navigate_focus.run_if(not(in_state(InGameState::Running))),
+            )
+            .add_systems(
+                Update,
+                apply_ui_scale.run_if(resource_changed::<AssistSettings>),
+            )
+            .add_systems(
+                Update,
+                (
+                    start_screen_fade.run_if(state_changed::<GameState>),
+                    update_screen_fade,
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), setup_main_menu)
+            .add_systems(
+                Update,
+                (button_interaction_system, animate_title).run_if(in_state(GameState::MainMenu)),
+            )
+            .add_systems(OnExit(GameState::MainMenu), cleanup_menu)
+            .add_systems(OnEnter(GameState::PlayerSelection), setup_player_selection)
+            .add_systems(
+                Update,
+                (
+                    type_profile_name,
+                    player_selection_button_system,
+                    refresh_profile_list.run_if(resource_changed::<Profiles>),
+                    highlight_current_profile,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::PlayerSelection)),
+            )
+            .add_systems(OnExit(GameState::PlayerSelection), cleanup_player_selection)
+            .add_systems(OnEnter(GameState::ModeSelect), setup_mode_select)
+            .add_systems(
+                Update,
+                (mode_select_button_system, highlight_selections)
+                    .chain()
+                    .run_if(in_state(GameState::ModeSelect)),
+            )
+            .add_systems(OnExit(GameState::ModeSelect), cleanup_mode_select)
+            .add_systems(OnEnter(GameState::RuleEditor), setup_rule_editor)
+            .add_systems(
+                Update,
+                (
+                    rule_editor_button_system,
+                    update_rule_values
+                        .run_if(resource_changed::<RuleDraft>.or(resource_changed::<StringTable>)),
+                    refresh_saved_rule_sets.run_if(resource_changed::<RuleDraft>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::RuleEditor)),
+            )
+            .add_systems(OnExit(GameState::RuleEditor), cleanup_rule_editor)
+            .add_systems(OnEnter(GameState::Credits), setup_credits)
+            .add_systems(
+                Update,
+                credits_button_system.run_if(in_state(GameState::Credits)),
+            )
+            .add_systems(OnExit(GameState::Credits), cleanup_credits)
+            .add_systems(OnEnter(GameState::AssetLoading), setup_loading_screen)
+            .add_systems(
+                Update,
+                update_loading_bar.run_if(in_state(GameState::AssetLoading)),
+            )
+            .add_systems(OnExit(GameState::AssetLoading), cleanup_loading_screen)
+            .add_systems(OnEnter(GameState::Settings), setup_settings_ui)
+            .add_systems(OnEnter(PauseMenu::Settings), setup_settings_ui)
+            .add_systems(
+                Update,
+                (
+                    settings_button_system,
+                    apply_volume_sliders,
+                    update_volume_labels.run_if(
+                        resource_changed::<VolumeSettings>.or(resource_changed::<StringTable>),
+                    ),
+                    update_option_labels.run_if(
+                        resource_changed::<TutorSettings>
+                            .or(resource_changed::<PaletteSettings>)
+                            .or(resource_changed::<AssistSettings>)
+                            .or(resource_changed::<StringTable>)
+                            .or(resource_changed::<CurrentLanguage>),
+                    ),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Settings).or(in_state(PauseMenu::Settings))),
+            )
+            .add_systems(OnExit(GameState::Settings), cleanup_settings)
+            .add_systems(OnExit(PauseMenu::Settings), cleanup_settings)
+            .add_systems(OnEnter(GameState::Stats), setup_stats_screen)
+            .add_systems(
+                Update,
+                stats_button_system.run_if(in_state(GameState::Stats)),
+            )
+            .add_systems(OnExit(GameState::Stats), cleanup_stats_screen)
+            .add_systems(OnEnter(GameState::InGame), setup_hud)
+            .add_systems(
+                Update,
+                (update_hud, update_round_info).run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_hud)
+            // Shows the round's score once it is on the leaderboard
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                setup_game_over.after(record_time_attack_score),
+            )
+            .add_systems(
+                Update,
+                game_over_button_system.run_if(in_state(GameState::GameOver)),
+            )
+            .add_systems(OnExit(GameState::GameOver), cleanup_game_over)
+            .add_systems(OnEnter(InGameState::Paused), pause_time)
+            .add_systems(OnExit(InGameState::Paused), resume_time)
+            .add_systems(OnEnter(PauseMenu::Main), setup_pause_menu)
+            .add_systems(
+                Update,
+                (
+                    toggle_pause.run_if(in_state(GameState::InGame)),
+                    pause_button_system.run_if(in_state(PauseMenu::Main)),
+                ),
+            )
+            .add_systems(OnExit(PauseMenu::Main), cleanup_pause_menu)
+            .add_systems(
+                OnEnter(InGameState::Running),
+                setup_replay_controls.run_if(resource_exists::<Playback>),
+            )
+            .add_systems(
+                Update,
+                (
+                    replay_button_system,
+                    replay_keyboard_controls,
+                    update_replay_controls,
+                )
+                    .run_if(in_state(InGameState::Running).and(resource_exists::<Playback>)),
+            )
+            .add_systems(OnExit(InGameState::Running), cleanup_replay_controls);
     }
 }
+
+fn apply_ui_scale(assist: Res<AssistSettings>, mut ui_scale: ResMut<UiScale>) {
+    ui_scale.0 = assist.ui_scale;
+}
@@ -0,0 +1,82 @@
+use crate::consts;
+use crate::localization::language::UiFont;
+use bevy::prelude::*;
+use bevy::ui::widget::NodeImageMode;
+
+/// Art, font and colors the UI widgets are drawn with. The images come from `assets/ui`
+/// and are nine-sliced, so they stretch to any size without blurring their borders.
+#[derive(Resource)]
+pub struct UiTheme {
+    pub panel: Handle<Image>,
+    pub button: Handle<Image>,
+    /// Button that is the current choice among several, e.g. the selected level.
+    pub button_selected: Handle<Image>,
+    pub checkbox_checked: Handle<Image>,
+    pub checkbox_empty: Handle<Image>,
+    pub bar_track: Handle<Image>,
+    pub bar_fill: Handle<Image>,
+    pub banner: Handle<Image>,
+    /// Follows `UiFont`, so widgets are spawned in the current language's font.
+    pub font: Handle<Font>,
+    /// Text on panels and banners.
+    pub text_color: Color,
+    /// Text on buttons, which are lighter than panels.
+    pub button_text_color: Color,
+}
+
+// Loaded when the plugin is built, since the first screen is spawned before `Startup`
+impl FromWorld for UiTheme {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            panel: asset_server.load("ui/panel_brown_dark.png"),
+            button: asset_server.load("ui/button_brown.png"),
+            button_selected: asset_server.load("ui/button_red.png"),
+            checkbox_checked: asset_server.load("ui/checkbox_brown_checked.png"),
+            checkbox_empty: asset_server.load("ui/checkbox_brown_empty.png"),
+            bar_track: asset_server.load("ui/progress_transparent.png"),
+            bar_fill: asset_server.load("ui/progress_green.png"),
+            banner: asset_server.load("ui/banner_hanging.png"),
+            font: Handle::default(),
+            text_color: consts::THEME_TEXT,
+            button_text_color: consts::THEME_BUTTON_TEXT,
+        }
+    }
+}
+
+impl UiTheme {
+    /// Font and color for text of `size` on a panel.
+    pub fn text(&self, size: f32) -> (TextFont, TextColor) {
+        (
+            TextFont {
+                font: self.font.clone(),
+                font_size: size,
+                ..default()
+            },
+            TextColor(self.text_color),
+        )
+    }
+
+    pub fn button_text(&self, size: f32) -> (TextFont, TextColor) {
+        (
+            TextFont {
+                font: self.font.clone(),
+                font_size: size,
+                ..default()
+            },
+            TextColor(self.button_text_color),
+        )
+    }
+}
+
+/// Nine-slices `image`, keeping `border` pixels on each side unstretched.
+pub fn sliced(image: Handle<Image>, border: f32) -> ImageNode {
+    ImageNode::new(image).with_mode(NodeImageMode::Sliced(TextureSlicer {
+        border: BorderRect::square(border),
+        ..default()
+    }))
+}
+
+pub fn sync_theme_font(ui_font: Res<UiFont>, mut theme: ResMut<UiTheme>) {
+    theme.font = ui_font.0.clone();
+}
@@ -0,0 +1,346 @@
+use crate::consts;
+use crate::game_ui::ui_theme::{sliced, UiTheme};
+use crate::localization::localized_text::LocalizedText;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+pub const BUTTON_HEIGHT: f32 = 55.0;
+pub const CHECKBOX_SIZE: f32 = 40.0;
+pub const BAR_HEIGHT: f32 = 24.0;
+/// Unstretched border of each nine-sliced image, in source pixels.
+const PANEL_BORDER: f32 = 24.0;
+const BUTTON_BORDER: f32 = 14.0;
+const BAR_BORDER: f32 = 11.0;
+const BANNER_BORDER: f32 = 40.0;
+
+/// Button drawn with the theme's button art. `selected` picks the highlighted variant.
+#[derive(Component, Default)]
+pub struct ThemedButton {
+    pub selected: bool,
+}
+
+#[derive(Component, PartialEq)]
+pub struct Checkbox(pub bool);
+
+/// Horizontal slider the mouse can drag, holding a value in `0.0..=1.0`.
+#[derive(Component, PartialEq)]
+pub struct Slider(pub f32);
+
+/// Bar filled to a fraction in `0.0..=1.0`, e.g. hp or loading progress.
+#[derive(Component, PartialEq)]
+pub struct ProgressBar(pub f32);
+
+/// Filled part of a `Slider` or `ProgressBar`.
+#[derive(Component)]
+pub struct BarFill;
+
+/// Line typed on the keyboard: letters, digits and spaces, up to `max_chars` of them.
+#[derive(Component)]
+pub struct TextField {
+    pub text: String,
+    pub max_chars: usize,
+}
+
+impl TextField {
+    pub fn new(max_chars: usize) -> Self {
+        Self {
+            text: String::new(),
+            max_chars,
+        }
+    }
+
+    /// The text with a trailing underscore as the cursor.
+    fn display(&self) -> String {
+        format!("{}_", self.text)
+    }
+}
+
+/// Themed panel background for a screen or box; goes next to the panel's `Node`.
+pub fn panel(theme: &UiTheme) -> ImageNode {
+    sliced(theme.panel.clone(), PANEL_BORDER)
+}
+
+/// Banner with a title, for the top of a screen. `label` is a string table key.
+pub fn spawn_banner(parent: &mut ChildBuilder, theme: &UiTheme, label: &str) {
+    parent
+        .spawn((
+            Node {
+                min_width: Val::Px(420.0),
+                height: Val::Px(100.0),
+                padding: UiRect::horizontal(Val::Px(50.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            sliced(theme.banner.clone(), BANNER_BORDER),
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::default(), LocalizedText::new(label), theme.text(44.0)));
+        });
+}
+
+/// Spawns a text button in the theme's style, tagged with `marker` (a component or
+/// several in a tuple). `label` is a string table key.
+pub fn spawn_text_button(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    label: &str,
+    width: f32,
+    marker: impl Bundle,
+) {
+    parent
+        .spawn((
+            Button,
+            ThemedButton::default(),
+            Node {
+                width: Val::Px(width),
+                height: Val::Px(BUTTON_HEIGHT),
+                // horizontally center child text
+                justify_content: JustifyContent::Center,
+                // vertically center child text
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            sliced(theme.button.clone(), BUTTON_BORDER),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(label),
+                theme.button_text(28.0),
+            ));
+        });
+}
+
+pub fn spawn_checkbox(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    checked: bool,
+    marker: impl Bundle,
+) {
+    let image = if checked {
+        &theme.checkbox_checked
+    } else {
+        &theme.checkbox_empty
+    };
+    parent.spawn((
+        Button,
+        Node {
+            width: Val::Px(CHECKBOX_SIZE),
+            height: Val::Px(CHECKBOX_SIZE),
+            ..default()
+        },
+        ImageNode::new(image.clone()),
+        Checkbox(checked),
+        marker,
+    ));
+}
+
+/// Track with a fill, shared by sliders and progress bars.
+fn spawn_bar(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    fraction: f32,
+    width: f32,
+    bundle: impl Bundle,
+) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(width),
+                height: Val::Px(BAR_HEIGHT),
+                ..default()
+            },
+            sliced(theme.bar_track.clone(), BAR_BORDER),
+            bundle,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Percent(fraction.clamp(0.0, 1.0) * 100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                sliced(theme.bar_fill.clone(), BAR_BORDER),
+                BarFill,
+            ));
+        });
+}
+
+pub fn spawn_slider(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    value: f32,
+    width: f32,
+    marker: impl Bundle,
+) {
+    spawn_bar(
+        parent,
+        theme,
+        value,
+        width,
+        (
+            Slider(value),
+            Interaction::default(),
+            RelativeCursorPosition::default(),
+            marker,
+        ),
+    );
+}
+
+pub fn spawn_progress_bar(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    fraction: f32,
+    width: f32,
+    marker: impl Bundle,
+) {
+    spawn_bar(
+        parent,
+        theme,
+        fraction,
+        width,
+        (ProgressBar(fraction), marker),
+    );
+}
+
+/// Spawns a text field, tagged with `marker` next to its `TextField`.
+pub fn spawn_text_field(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    field: TextField,
+    width: f32,
+    marker: impl Bundle,
+) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(width),
+                height: Val::Px(BUTTON_HEIGHT),
+                padding: UiRect::horizontal(Val::Px(12.0)),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(consts::INPUT_FIELD_BG),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(field.display()),
+                theme.button_text(26.0),
+                field,
+                marker,
+            ));
+        });
+}
+
+fn interaction_tint(interaction: Interaction) -> Color {
+    match interaction {
+        Interaction::Pressed => consts::PRESSED_TINT,
+        Interaction::Hovered => consts::HOVERED_TINT,
+        Interaction::None => Color::WHITE,
+    }
+}
+
+pub fn update_button_visuals(
+    theme: Res<UiTheme>,
+    mut buttons: Query<
+        (&Interaction, &ThemedButton, &mut ImageNode),
+        Or<(Changed<Interaction>, Changed<ThemedButton>)>,
+    >,
+) {
+    for (interaction, button, mut image) in buttons.iter_mut() {
+        image.image = if button.selected {
+            theme.button_selected.clone()
+        } else {
+            theme.button.clone()
+        };
+        image.color = interaction_tint(*interaction);
+    }
+}
+
+pub fn update_checkbox_visuals(
+    theme: Res<UiTheme>,
+    mut checkboxes: Query<
+        (&Interaction, &Checkbox, &mut ImageNode),
+        Or<(Changed<Interaction>, Changed<Checkbox>)>,
+    >,
+) {
+    for (interaction, checkbox, mut image) in checkboxes.iter_mut() {
+        image.image = if checkbox.0 {
+            theme.checkbox_checked.clone()
+        } else {
+            theme.checkbox_empty.clone()
+        };
+        image.color = interaction_tint(*interaction);
+    }
+}
+
+/// Sets a slider to where the mouse is while it is held down on it.
+pub fn drag_sliders(mut sliders: Query<(&Interaction, &RelativeCursorPosition, &mut Slider)>) {
+    for (interaction, cursor, mut slider) in sliders.iter_mut() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        // Normalized positions are centered on the node, running from -0.5 to 0.5
+        if let Some(position) = cursor.normalized {
+            slider.set_if_neq(Slider((position.x + 0.5).clamp(0.0, 1.0)));
+        }
+    }
+}
+
+pub fn update_bar_fills(
+    bars: Query<
+        (&Children, Option<&Slider>, Option<&ProgressBar>),
+        Or<(Changed<Slider>, Changed<ProgressBar>)>,
+    >,
+    mut fills: Query<&mut Node, With<BarFill>>,
+) {
+    for (children, slider, progress) in bars.iter() {
+        let fraction = slider.map(|s| s.0).or(progress.map(|p| p.0)).unwrap_or(0.0);
+        for child in children.iter() {
+            if let Ok(mut node) = fills.get_mut(*child) {
+                node.width = Val::Percent(fraction.clamp(0.0, 1.0) * 100.0);
+            }
+        }
+    }
+}
+
+/// Types into every text field on screen; Backspace deletes.
+pub fn type_into_text_fields(
+    mut key_events: EventReader<KeyboardInput>,
+    mut fields: Query<&mut TextField>,
+) {
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        for mut field in fields.iter_mut() {
+            let room = field.text.chars().count() < field.max_chars;
+            match &event.logical_key {
+                Key::Backspace => {
+                    field.text.pop();
+                }
+                Key::Space if room => field.text.push(' '),
+                Key::Character(typed) => {
+                    for character in typed.chars() {
+                        if character.is_alphanumeric()
+                            && field.text.chars().count() < field.max_chars
+                        {
+                            field.text.push(character);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn update_text_fields(mut fields: Query<(&TextField, &mut Text), Changed<TextField>>) {
+    for (field, mut text) in fields.iter_mut() {
+        text.0 = field.display();
+    }
+}
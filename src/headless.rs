@@ -0,0 +1,124 @@
+//! Running the game without a window, for tests and the `--headless` command line mode.
+
+use crate::animations::asset_loader::ImageAssets;
+use crate::animations::player_animation_state::PlayerState;
+use crate::engine::game_runner::GameRunnerPlugin;
+use crate::engine::player::{Player, PlayerStatus};
+use crate::environment::level_asset::CurrentLevel;
+use crate::environment::play_area::PlayArea;
+use crate::replay::playback::PendingReplay;
+use crate::replay::replay_plugin::ReplayPlugin;
+use crate::GameState;
+use bevy::app::{AppExit, ScheduleRunnerPlugin};
+use bevy::core::FrameCount;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::scene::ScenePlugin;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Runs the gameplay plugins without a window, renderer or audio device.
+///
+/// Every `App::update` advances the game by exactly `timestep`, and the level spans
+/// `play_area` instead of the window, so a round plays out the same way on any machine.
+/// The app starts in `GameState::AssetLoading` and moves on to `InGame` once the selected
+/// level has loaded, so the level is built on the first frame of every round.
+pub struct HeadlessPlugin {
+    /// Size of the level, in world units.
+    pub play_area: Vec2,
+    /// Game time each `App::update` advances by.
+    pub timestep: Duration,
+}
+
+impl Default for HeadlessPlugin {
+    fn default() -> Self {
+        Self {
+            play_area: PlayArea::default().size,
+            timestep: Duration::from_secs_f64(1.0 / 60.0),
+        }
+    }
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::ZERO)),
+            StatesPlugin,
+            TransformPlugin,
+            HierarchyPlugin,
+            AssetPlugin::default(),
+            InputPlugin,
+            // avian2d looks colliders up in spawned scenes
+            ScenePlugin,
+        ))
+        .init_asset::<Image>()
+        .init_asset::<TextureAtlasLayout>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(self.timestep))
+        .insert_resource(ImageAssets::placeholder())
+        .add_plugins((GameRunnerPlugin, ReplayPlugin))
+        .insert_resource(PlayArea {
+            size: self.play_area,
+        })
+        .add_systems(Startup, start_round)
+        .add_systems(
+            Update,
+            wait_for_level
+                .run_if(in_state(GameState::AssetLoading).and(resource_exists::<CurrentLevel>)),
+        );
+    }
+}
+
+fn start_round(mut game_state: ResMut<NextState<GameState>>) {
+    game_state.set(GameState::AssetLoading);
+}
+
+/// Stands in for the sprite loading state, which headless runs skip.
+fn wait_for_level(
+    asset_server: Res<AssetServer>,
+    current_level: Res<CurrentLevel>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if asset_server.is_loaded_with_dependencies(&current_level.handle) {
+        game_state.set(GameState::InGame);
+    }
+}
+
+/// Entry point for `--headless [--frames N] [--replay FILE]`: plays N frames, optionally
+/// feeding in a recorded round, and prints where the player ended up.
+pub fn run(frames: u32, replay: Option<PathBuf>) -> AppExit {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_resource(FrameLimit(frames))
+        .add_systems(Last, exit_after_frame_limit);
+    if let Some(path) = replay {
+        app.insert_resource(PendingReplay(path));
+    }
+    app.run()
+}
+
+#[derive(Resource)]
+struct FrameLimit(u32);
+
+fn exit_after_frame_limit(
+    frame_count: Res<FrameCount>,
+    limit: Res<FrameLimit>,
+    players: Query<(&Transform, &PlayerState, &PlayerStatus), With<Player>>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    if frame_count.0 < limit.0 {
+        return;
+    }
+    for (transform, state, status) in players.iter() {
+        println!(
+            "Player at {:?}, state {:?}, hp {}, points {}, pot {}",
+            transform.translation.truncate(),
+            state.current_state(),
+            status.hp,
+            status.points,
+            status.pot
+        );
+    }
+    app_exit.send(AppExit::Success);
+}
@@ -0,0 +1,113 @@
+//! WackyCrates: a 2D platformer where players strike crates for points while every new
+//! crate is a little more likely to explode.
+//!
+//! Add [`WackyCratesPlugin`] next to Bevy's `DefaultPlugins` to embed the full game, or
+//! [`HeadlessPlugin`] on its own to drive the gameplay from tests without a window.
+
+use bevy::prelude::*;
+
+mod accessibility;
+mod animations;
+mod audio;
+mod consts;
+mod data;
+// Developer tools stay out of release builds
+#[cfg(debug_assertions)]
+mod debug;
+mod effects;
+mod engine;
+mod environment;
+mod game_ui;
+pub mod headless;
+mod localization;
+mod profile;
+mod replay;
+mod telemetry;
+mod tutor;
+
+pub use animations::player_animation_state::{PlayerState, PlayerStateKind};
+pub use engine::game_runner::GameRunnerPlugin;
+pub use engine::gameplay_events::{DamageEvent, GameplayEvent};
+pub use engine::player::{AddPlayerPlugin, Player, PlayerStatus};
+pub use engine::player_input::{MoveDirection, PlayerInputs};
+pub use environment::crate_spawner::{CrateRisk, WackyCrate};
+pub use environment::environment_plugin::SpawnEnvironmentsPlugin;
+pub use environment::platform_spawner::{Platform, PlatformLayer};
+pub use game_ui::ui_events::UiEvent;
+pub use headless::HeadlessPlugin;
+pub use replay::playback::PendingReplay;
+pub use replay::replay_plugin::ReplayPlugin;
+pub use telemetry::telemetry_plugin::TelemetryPlugin;
+pub use telemetry::telemetry_record::TelemetryFormat;
+pub use telemetry::telemetry_sink::TelemetrySettings;
+
+/// Top-level flow of the game. Gameplay only runs in `InGame`.
+#[derive(Debug, Eq, PartialEq, Hash, Resource, States, Default, Clone)]
+pub enum GameState {
+    #[default]
+    MainMenu,
+    /// Choosing or creating the profile progress is saved under.
+    PlayerSelection,
+    /// Picking a game mode, level and difficulty before a round.
+    ModeSelect,
+    /// Editing, saving and loading custom rule sets.
+    RuleEditor,
+    Credits,
+    /// Loading sprite sheets and the selected level before a round starts.
+    AssetLoading,
+    Settings,
+    /// Charts computed from saved telemetry.
+    Stats,
+    InGame,
+    /// Score and leaderboard of the round that just ended.
+    GameOver,
+}
+
+/// What the game is doing while `GameState::InGame` is active.
+#[derive(SubStates, Debug, Eq, PartialEq, Hash, Default, Clone)]
+#[source(GameState = GameState::InGame)]
+pub enum InGameState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Which screen of the pause overlay is showing.
+#[derive(SubStates, Debug, Eq, PartialEq, Hash, Default, Clone)]
+#[source(InGameState = InGameState::Paused)]
+pub enum PauseMenu {
+    #[default]
+    Main,
+    Settings,
+}
+
+/// The complete game: menus, gameplay, effects and audio. Expects `DefaultPlugins`.
+pub struct WackyCratesPlugin;
+
+impl Plugin for WackyCratesPlugin {
+    fn build(&self, app: &mut App) {
+        // GameRunnerPlugin goes first, as it sets up the game states the others rely on
+        app.add_plugins((
+            GameRunnerPlugin,
+            animations::asset_loader::GameAssetsPlugin,
+            effects::effects_plugin::EffectsPlugin,
+            game_ui::ui_plugin::UiPlugin,
+            audio::audio_plugin::GameAudioPlugin,
+            ReplayPlugin,
+            TelemetryPlugin,
+            localization::localization_plugin::LocalizationPlugin,
+            tutor::tutor_plugin::TutorPlugin,
+            accessibility::accessibility_plugin::AccessibilityPlugin,
+            profile::profile_plugin::ProfilePlugin,
+        ));
+        // Developer tools stay out of release builds
+        #[cfg(debug_assertions)]
+        app.add_plugins(debug::debug_plugin::DebugInspectorPlugin);
+    }
+}
+
+pub(crate) fn add_game_states(app: &mut App) {
+    app.init_state::<GameState>()
+        .add_sub_state::<InGameState>()
+        .add_sub_state::<PauseMenu>();
+}
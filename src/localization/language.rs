@@ -0,0 +1,89 @@
+use super::string_table::StringTableHandle;
+use bevy::prelude::*;
+
+/// Writing system a language needs glyphs for. Decides which font UI text uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    /// Unaccented Latin letters, covered by the display fonts in `assets/fonts`.
+    Latin,
+    /// Latin with accents and other diacritics.
+    LatinExtended,
+}
+
+impl Script {
+    /// Font for this script under `assets/`, or `None` for Bevy's built-in font. The
+    /// display fonts in `assets/fonts` only contain ASCII, so accented text falls back to
+    /// the built-in font until a font with those glyphs is added there.
+    pub fn font_path(self) -> Option<&'static str> {
+        match self {
+            Script::Latin => Some("fonts/Glowdex.ttf"),
+            Script::LatinExtended => None,
+        }
+    }
+}
+
+pub struct LanguageEntry {
+    /// Name shown in the language picker, in the language itself.
+    pub name: &'static str,
+    pub path: &'static str,
+    pub script: Script,
+}
+
+/// Languages offered in Settings.
+#[derive(Resource)]
+pub struct LanguageCatalog(pub Vec<LanguageEntry>);
+
+impl Default for LanguageCatalog {
+    fn default() -> Self {
+        Self(vec![
+            LanguageEntry {
+                name: "English",
+                path: "text/en.strings.ron",
+                script: Script::Latin,
+            },
+            LanguageEntry {
+                name: "Español",
+                path: "text/es.strings.ron",
+                script: Script::LatinExtended,
+            },
+            LanguageEntry {
+                name: "Français",
+                path: "text/fr.strings.ron",
+                script: Script::LatinExtended,
+            },
+        ])
+    }
+}
+
+/// Index into the `LanguageCatalog` of the language the UI is shown in.
+#[derive(Resource, Default)]
+pub struct CurrentLanguage(pub usize);
+
+impl CurrentLanguage {
+    pub fn next(&self, catalog: &LanguageCatalog) -> Self {
+        Self((self.0 + 1) % catalog.0.len().max(1))
+    }
+}
+
+/// Font all UI text is drawn with, matching the current language's script.
+#[derive(Resource, Default)]
+pub struct UiFont(pub Handle<Font>);
+
+/// Loads the string table and picks the font for the current language. Runs at startup
+/// and whenever the language changes.
+pub fn apply_language(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    catalog: Res<LanguageCatalog>,
+    language: Res<CurrentLanguage>,
+    mut ui_font: ResMut<UiFont>,
+) {
+    let Some(entry) = catalog.0.get(language.0).or(catalog.0.first()) else {
+        return;
+    };
+    commands.insert_resource(StringTableHandle(asset_server.load(entry.path)));
+    ui_font.0 = entry
+        .script
+        .font_path()
+        .map_or_else(Handle::default, |path| asset_server.load(path));
+}
@@ -0,0 +1,29 @@
+use super::language::*;
+use super::localized_text::*;
+use super::string_table::*;
+use crate::data::ron_asset::RonAssetAppExt;
+use bevy::prelude::*;
+
+/// Loads the string table for the selected language and keeps on-screen text and fonts
+/// in sync with it, so the language can change at any time.
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_ron_asset::<StringTable>()
+            .init_resource::<StringTable>()
+            .init_resource::<LanguageCatalog>()
+            .init_resource::<CurrentLanguage>()
+            .init_resource::<UiFont>()
+            .add_systems(
+                Update,
+                (
+                    apply_language.run_if(resource_changed::<CurrentLanguage>),
+                    sync_string_table,
+                    localize_texts,
+                    apply_ui_font.run_if(resource_changed::<UiFont>),
+                )
+                    .chain(),
+            );
+    }
+}
@@ -0,0 +1,32 @@
+use super::language::UiFont;
+use super::string_table::StringTable;
+use bevy::prelude::*;
+
+/// UI text that shows the string table entry for this key and follows language changes.
+/// Keys without an entry show as-is, which suits symbols like "+" or "<<".
+#[derive(Component)]
+pub struct LocalizedText(pub String);
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+}
+
+pub fn localize_texts(
+    strings: Res<StringTable>,
+    mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    for (localized, mut text) in texts.iter_mut() {
+        if strings.is_changed() || localized.is_changed() {
+            text.0 = strings.get(&localized.0).to_string();
+        }
+    }
+}
+
+/// Switches text already on screen to the new language's font.
+pub fn apply_ui_font(ui_font: Res<UiFont>, mut fonts: Query<&mut TextFont, With<Text>>) {
+    for mut font in fonts.iter_mut() {
+        font.font = ui_font.0.clone();
+    }
+}
@@ -0,0 +1,4 @@
+pub mod language;
+pub mod localization_plugin;
+pub mod localized_text;
+pub mod string_table;
@@ -0,0 +1,117 @@
+use crate::data::ron_asset::RonAsset;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Player-facing text keyed by id, read from `assets/text/*.strings.ron`. Values may
+/// contain `{name}` placeholders filled in by [`StringTable::format`].
+#[derive(Asset, TypePath, Resource, Clone, Default, Deserialize)]
+pub struct StringTable {
+    pub strings: HashMap<String, String>,
+}
+
+impl RonAsset for StringTable {
+    const EXTENSIONS: &'static [&'static str] = &["strings.ron"];
+}
+
+impl StringTable {
+    /// Text for `key`, or the key itself so missing entries are easy to spot.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map_or(key, String::as_str)
+    }
+
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        args.iter()
+            .fold(self.get(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+}
+
+/// Table of the current language. Replaced by `apply_language` when the language changes.
+#[derive(Resource)]
+pub struct StringTableHandle(pub Handle<StringTable>);
+
+/// Copies the current language's table into the `StringTable` resource once it has
+/// loaded, and again on every edit.
+pub fn sync_string_table(
+    mut events: EventReader<AssetEvent<StringTable>>,
+    tables: Res<Assets<StringTable>>,
+    handle: Option<Res<StringTableHandle>>,
+    mut strings: ResMut<StringTable>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+    // A table that was already loaded sends no new event when switching back to it
+    let mut apply = handle.is_changed();
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            apply |= *id == handle.0.id();
+        }
+    }
+    if apply {
+        if let Some(loaded) = tables.get(&handle.0) {
+            *strings = loaded.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::localization::language::LanguageCatalog;
+
+    fn table(entries: &[(&str, &str)]) -> StringTable {
+        StringTable {
+            strings: entries
+                .iter()
+                .map(|(key, text)| (key.to_string(), text.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn missing_keys_show_the_key() {
+        let strings = table(&[("menu.play", "Play")]);
+        assert_eq!(strings.get("menu.play"), "Play");
+        assert_eq!(strings.get("menu.missing"), "menu.missing");
+    }
+
+    #[test]
+    fn format_fills_every_placeholder() {
+        let strings = table(&[("hud.score", "{points} points, {points} total, pot {pot}")]);
+        assert_eq!(
+            strings.format(
+                "hud.score",
+                &[("points", "12".to_string()), ("pot", "3".to_string())]
+            ),
+            "12 points, 12 total, pot 3"
+        );
+        // Unknown placeholders are left for the reader to spot
+        assert_eq!(
+            strings.format("hud.score", &[]),
+            "{points} points, {points} total, pot {pot}"
+        );
+    }
+
+    #[test]
+    fn every_language_has_the_english_keys() {
+        let load = |path: &str| -> StringTable {
+            let contents = std::fs::read_to_string(format!("assets/{path}")).unwrap();
+            ron::from_str(&contents).unwrap_or_else(|err| panic!("{path}: {err}"))
+        };
+        let catalog = LanguageCatalog::default();
+        let english = load(catalog.0[0].path);
+        for language in &catalog.0[1..] {
+            let strings = load(language.path);
+            let mut missing: Vec<_> = english
+                .strings
+                .keys()
+                .filter(|key| !strings.strings.contains_key(*key))
+                .collect();
+            missing.sort();
+            assert!(missing.is_empty(), "{} lacks {missing:?}", language.name);
+        }
+    }
+}
@@ -1,27 +1,36 @@
 use bevy::prelude::*;
-use game_ui::ui_plugin::UiPlugin;
+use std::path::PathBuf;
+use wacky_crates::{headless, PendingReplay, TelemetrySettings, WackyCratesPlugin};
 
-mod animations;
-mod consts;
-mod engine;
-mod environment;
-mod game_ui;
+/// Frames played by `--headless` when `--frames` isn't given.
+const DEFAULT_HEADLESS_FRAMES: u32 = 600;
 
-#[derive(Debug, Eq, PartialEq, Hash, Resource, States, Default, Clone)]
-enum GameState {
-    #[default]
-    MainMenu,
-    AssetLoading,
-    Settings,
-    InGame,
-    _GameOver,
+fn main() -> AppExit {
+    let args: Vec<String> = std::env::args().collect();
+    let replay = flag_value(&args, "--replay").map(PathBuf::from);
+    if args.iter().any(|arg| arg == "--headless") {
+        let frames = flag_value(&args, "--frames")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_HEADLESS_FRAMES);
+        return headless::run(frames, replay);
+    }
+
+    let telemetry = flag_value(&args, "--telemetry")
+        .and_then(|value| value.parse().map_err(|error| eprintln!("{error}")).ok());
+
+    let mut app = App::new();
+    app.add_plugins((DefaultPlugins, WackyCratesPlugin))
+        .insert_resource(TelemetrySettings { format: telemetry });
+    if let Some(path) = replay {
+        app.insert_resource(PendingReplay(path));
+    }
+    app.run()
 }
 
-fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .add_plugins(engine::game_runner::GameRunnerPlugin)
-        .init_state::<GameState>()
-        .add_plugins(UiPlugin)
-        .run();
+/// The argument following `flag`, e.g. the path in `--replay <path>`.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
 }
@@ -0,0 +1,156 @@
+use super::profile_store::*;
+use super::profiles::Profiles;
+use crate::engine::game_mode::GameMode;
+use crate::engine::round_end::RoundResult;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const LEADERBOARD_FILE: &str = "time_attack.ron";
+/// Entries shown in each list on the game over screen.
+pub const LEADERBOARD_SHOWN: usize = 5;
+
+/// One finished Time Attack round.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeaderboardEntry {
+    pub profile: String,
+    pub seed: u64,
+    pub level: String,
+    pub score: u32,
+}
+
+/// Every Time Attack round finished on this computer, best score first.
+///
+/// A seed only replays the same crates on the same level, so seed boards match on both.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// Inserts `entry` below any earlier entry with the same score.
+    pub fn add(&mut self, entry: LeaderboardEntry) {
+        let position = self
+            .entries
+            .partition_point(|existing| existing.score >= entry.score);
+        self.entries.insert(position, entry);
+    }
+
+    pub fn for_profile<'a>(
+        &'a self,
+        profile: &'a str,
+    ) -> impl Iterator<Item = &'a LeaderboardEntry> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.profile == profile)
+    }
+
+    pub fn for_seed<'a>(
+        &'a self,
+        seed: u64,
+        level: &'a str,
+    ) -> impl Iterator<Item = &'a LeaderboardEntry> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.seed == seed && entry.level == level)
+    }
+
+    /// Reads `profiles/time_attack.ron`, starting empty when it doesn't exist yet.
+    pub fn load() -> Result<Self, ProfileError> {
+        let mut leaderboard = load_profile_file::<Self>(LEADERBOARD_FILE)?.unwrap_or_default();
+        // Keep the order `add` relies on, even if the file was edited by hand
+        leaderboard
+            .entries
+            .sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        Ok(leaderboard)
+    }
+
+    pub fn save(&self) -> Result<(), ProfileError> {
+        save_profile_file(LEADERBOARD_FILE, self)
+    }
+}
+
+/// Adds a finished Time Attack round to the leaderboard under the current profile.
+/// Watched replays aren't recorded.
+pub fn record_time_attack_score(
+    result: Res<RoundResult>,
+    profiles: Res<Profiles>,
+    mut leaderboard: ResMut<Leaderboard>,
+) {
+    if result.mode != GameMode::TimeAttack {
+        return;
+    }
+    leaderboard.add(LeaderboardEntry {
+        profile: profiles.current_name().to_string(),
+        seed: result.seed,
+        level: result.level.clone(),
+        score: result.score,
+    });
+    if let Err(error) = leaderboard.save() {
+        warn!("{error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(profile: &str, seed: u64, score: u32) -> LeaderboardEntry {
+        LeaderboardEntry {
+            profile: profile.to_string(),
+            seed,
+            level: "levels/classic.level.ron".to_string(),
+            score,
+        }
+    }
+
+    fn scores(leaderboard: &Leaderboard) -> Vec<(&str, u32)> {
+        leaderboard
+            .entries
+            .iter()
+            .map(|entry| (entry.profile.as_str(), entry.score))
+            .collect()
+    }
+
+    #[test]
+    fn add_keeps_best_scores_first() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.add(entry("ada", 1, 20));
+        leaderboard.add(entry("ada", 1, 50));
+        leaderboard.add(entry("bo", 2, 5));
+        leaderboard.add(entry("bo", 1, 30));
+
+        assert_eq!(
+            scores(&leaderboard),
+            [("ada", 50), ("bo", 30), ("ada", 20), ("bo", 5)]
+        );
+    }
+
+    #[test]
+    fn add_places_ties_below_earlier_entries() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.add(entry("ada", 1, 30));
+        leaderboard.add(entry("bo", 1, 30));
+        leaderboard.add(entry("cy", 1, 30));
+
+        assert_eq!(scores(&leaderboard), [("ada", 30), ("bo", 30), ("cy", 30)]);
+    }
+
+    #[test]
+    fn boards_filter_by_profile_and_seed() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.add(entry("ada", 1, 20));
+        leaderboard.add(entry("bo", 1, 30));
+        leaderboard.add(entry("ada", 2, 40));
+        let mut other_level = entry("bo", 1, 50);
+        other_level.level = "levels/staircase.level.ron".to_string();
+        leaderboard.add(other_level);
+
+        let ada: Vec<u32> = leaderboard.for_profile("ada").map(|e| e.score).collect();
+        assert_eq!(ada, [40, 20]);
+        let seed: Vec<u32> = leaderboard
+            .for_seed(1, "levels/classic.level.ron")
+            .map(|e| e.score)
+            .collect();
+        assert_eq!(seed, [30, 20]);
+    }
+}
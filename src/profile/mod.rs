@@ -0,0 +1,4 @@
+pub mod leaderboard;
+pub mod profile_plugin;
+pub mod profile_store;
+pub mod profiles;
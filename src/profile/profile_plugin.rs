@@ -0,0 +1,35 @@
+use super::leaderboard::*;
+use super::profiles::*;
+use crate::engine::round_end::RoundResult;
+use crate::replay::playback::Playback;
+use crate::GameState;
+use bevy::prelude::*;
+
+/// Player profiles and their Time Attack leaderboard, read when the plugin is built and
+/// saved whenever they change.
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        let profiles = Profiles::load().unwrap_or_else(|error| {
+            warn!("{error}");
+            Profiles::default()
+        });
+        let leaderboard = Leaderboard::load().unwrap_or_else(|error| {
+            warn!("{error}");
+            Leaderboard::default()
+        });
+        app.insert_resource(profiles)
+            .insert_resource(leaderboard)
+            .add_systems(
+                Update,
+                save_profiles
+                    .run_if(resource_changed::<Profiles>.and(not(resource_added::<Profiles>))),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                record_time_attack_score
+                    .run_if(resource_exists::<RoundResult>.and(not(resource_exists::<Playback>))),
+            );
+    }
+}
@@ -0,0 +1,35 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+use thiserror::Error;
+
+/// Directory, relative to the working directory, where profiles and their records live.
+pub const PROFILE_DIR: &str = "profiles";
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("Could not access profile file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse profile file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Could not write profile file: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+/// Reads `profiles/<file_name>`, or returns `None` if it doesn't exist yet.
+pub fn load_profile_file<T: DeserializeOwned>(file_name: &str) -> Result<Option<T>, ProfileError> {
+    let contents = match std::fs::read_to_string(Path::new(PROFILE_DIR).join(file_name)) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+    Ok(Some(ron::from_str(&contents)?))
+}
+
+pub fn save_profile_file<T: Serialize>(file_name: &str, value: &T) -> Result<(), ProfileError> {
+    std::fs::create_dir_all(PROFILE_DIR)?;
+    let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())?;
+    std::fs::write(Path::new(PROFILE_DIR).join(file_name), contents)?;
+    Ok(())
+}
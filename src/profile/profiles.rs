@@ -0,0 +1,66 @@
+use super::profile_store::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const PROFILE_FILE: &str = "profiles.ron";
+/// Longest name that can be typed for a new profile, in characters.
+pub const MAX_PROFILE_NAME: usize = 16;
+
+/// Everyone who plays on this computer, e.g. the pupils of a class, and who is playing now.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct Profiles {
+    pub names: Vec<String>,
+    /// Index into `names` of the profile playing now.
+    pub current: usize,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            names: vec!["Player 1".to_string()],
+            current: 0,
+        }
+    }
+}
+
+impl Profiles {
+    pub fn current_name(&self) -> &str {
+        self.names.get(self.current).map_or("", String::as_str)
+    }
+
+    /// Adds a profile and makes it the current one. Returns false, changing nothing, if
+    /// the name is blank or already taken.
+    pub fn add(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.names.iter().any(|existing| existing == name) {
+            return false;
+        }
+        self.names.push(name.to_string());
+        self.current = self.names.len() - 1;
+        true
+    }
+
+    /// Reads `profiles/profiles.ron`, falling back to a single default profile when it
+    /// doesn't exist yet.
+    pub fn load() -> Result<Self, ProfileError> {
+        let Some(mut profiles) = load_profile_file::<Self>(PROFILE_FILE)? else {
+            return Ok(Self::default());
+        };
+        if profiles.names.is_empty() {
+            profiles = Self::default();
+        }
+        profiles.current = profiles.current.min(profiles.names.len() - 1);
+        Ok(profiles)
+    }
+
+    pub fn save(&self) -> Result<(), ProfileError> {
+        save_profile_file(PROFILE_FILE, self)
+    }
+}
+
+/// Writes the profile list back to disk whenever it changes.
+pub fn save_profiles(profiles: Res<Profiles>) {
+    if let Err(error) = profiles.save() {
+        warn!("{error}");
+    }
+}
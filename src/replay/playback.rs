@@ -0,0 +1,319 @@
+use super::replay_file::Replay;
+use crate::data::rule_set::{RuleChoice, SelectedRules};
+use crate::engine::assist::{AssistSettings, NextRoundExtraHp};
+use crate::engine::debug_action::DebugAction;
+use crate::engine::game_mode::{GameMode, SelectedMode};
+use crate::engine::player_input::{PlayerInputs, ScriptedInput};
+use crate::engine::simulation::{NextRoundSeed, SimulationTick};
+use crate::environment::level_asset::{LevelCatalog, SelectedLevel};
+use crate::GameState;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Relative game speed while fast-forwarding to a seek target.
+pub const SEEK_SPEED: f32 = 8.0;
+/// How far one scrub step jumps, in simulation ticks.
+pub const SCRUB_TICKS: u64 = 300;
+
+/// A replay being watched. Inputs are fed back on the tick they were recorded on.
+#[derive(Resource)]
+pub struct Playback {
+    pub replay: Replay,
+    pub cursor: usize,
+    /// Next entry of `replay.debug_actions` to play back.
+    pub debug_cursor: usize,
+    pub paused: bool,
+    /// Tick to fast-forward to before resuming normal speed.
+    pub seek_to: Option<u64>,
+}
+
+/// Replay file to start watching as soon as the app is up, e.g. from `--replay <file>`.
+#[derive(Resource)]
+pub struct PendingReplay(pub PathBuf);
+
+/// The level, mode and rules the player had picked before watching a replay. Put back
+/// once they leave for the main menu.
+#[derive(Resource)]
+pub struct SavedChoices {
+    pub level: usize,
+    pub mode: GameMode,
+    pub rules: RuleChoice,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub enum PlaybackCommand {
+    TogglePause,
+    ScrubBack,
+    ScrubForward,
+}
+
+/// Starts watching `replay` from the beginning.
+pub fn start_playback(commands: &mut Commands, replay: Replay) {
+    commands.insert_resource(Playback {
+        replay,
+        cursor: 0,
+        debug_cursor: 0,
+        paused: false,
+        seek_to: None,
+    });
+    commands.insert_resource(ScriptedInput);
+}
+
+pub fn start_pending_replay(
+    mut commands: Commands,
+    pending: Option<Res<PendingReplay>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+    match Replay::load(&pending.0) {
+        Ok(replay) => {
+            start_playback(&mut commands, replay);
+            game_state.set(GameState::AssetLoading);
+        }
+        Err(error) => warn!("{error}"),
+    }
+    commands.remove_resource::<PendingReplay>();
+}
+
+/// Makes the next round use the replay's seed, level, mode, rules and extra hp, keeping
+/// the player's own choices in [`SavedChoices`].
+pub fn prepare_playback_round(
+    mut commands: Commands,
+    playback: Res<Playback>,
+    saved: Option<Res<SavedChoices>>,
+    catalog: Res<LevelCatalog>,
+    mut selected: ResMut<SelectedLevel>,
+    mut mode: ResMut<SelectedMode>,
+    mut rules: ResMut<SelectedRules>,
+    mut next_seed: ResMut<NextRoundSeed>,
+    mut next_extra_hp: ResMut<NextRoundExtraHp>,
+) {
+    // Scrubbing back reloads the round with the replay's choices already selected
+    if saved.is_none() {
+        commands.insert_resource(SavedChoices {
+            level: selected.0,
+            mode: mode.0,
+            rules: rules.0.clone(),
+        });
+    }
+    next_seed.0 = Some(playback.replay.seed);
+    next_extra_hp.0 = Some(playback.replay.extra_hp);
+    mode.0 = playback.replay.mode;
+    rules.0 = playback.replay.rules.clone();
+    if let Some(index) = catalog
+        .0
+        .iter()
+        .position(|entry| entry.path == playback.replay.level)
+    {
+        selected.0 = index;
+    }
+}
+
+pub fn rewind_playback(mut playback: ResMut<Playback>) {
+    playback.cursor = 0;
+    playback.debug_cursor = 0;
+}
+
+pub fn inject_replay_inputs(
+    mut playback: ResMut<Playback>,
+    tick: Res<SimulationTick>,
+    mut input_events: EventWriter<PlayerInputs>,
+    mut debug_actions: EventWriter<DebugAction>,
+) {
+    while let Some(recorded) = playback.replay.inputs.get(playback.cursor) {
+        if recorded.tick > tick.0 {
+            break;
+        }
+        input_events.send(recorded.input.clone());
+        playback.cursor += 1;
+    }
+    while let Some(recorded) = playback.replay.debug_actions.get(playback.debug_cursor) {
+        if recorded.tick > tick.0 {
+            break;
+        }
+        debug_actions.send(recorded.action.clone());
+        playback.debug_cursor += 1;
+    }
+}
+
+pub fn handle_playback_commands(
+    mut commands_reader: EventReader<PlaybackCommand>,
+    mut playback: ResMut<Playback>,
+    tick: Res<SimulationTick>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for command in commands_reader.read() {
+        match command {
+            PlaybackCommand::TogglePause => {
+                playback.paused = !playback.paused;
+                if playback.paused {
+                    virtual_time.pause();
+                } else {
+                    virtual_time.unpause();
+                }
+            }
+            PlaybackCommand::ScrubForward => {
+                let from = playback.seek_to.unwrap_or(tick.0);
+                playback.seek_to = Some(from + SCRUB_TICKS);
+                virtual_time.unpause();
+            }
+            PlaybackCommand::ScrubBack => {
+                // The simulation can't run backwards, so replay the round up to the target
+                let from = playback.seek_to.unwrap_or(tick.0);
+                playback.seek_to = Some(from.saturating_sub(SCRUB_TICKS));
+                virtual_time.unpause();
+                game_state.set(GameState::AssetLoading);
+            }
+        }
+    }
+}
+
+pub fn update_seek(
+    mut playback: ResMut<Playback>,
+    tick: Res<SimulationTick>,
+    assist: Res<AssistSettings>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let Some(target) = playback.seek_to else {
+        return;
+    };
+    if tick.0 < target {
+        virtual_time.set_relative_speed(SEEK_SPEED);
+        return;
+    }
+    playback.seek_to = None;
+    virtual_time.set_relative_speed(assist.game_speed);
+    if playback.paused {
+        virtual_time.pause();
+    }
+}
+
+pub fn stop_playback(
+    mut commands: Commands,
+    assist: Res<AssistSettings>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    saved: Option<Res<SavedChoices>>,
+    mut selected: ResMut<SelectedLevel>,
+    mut mode: ResMut<SelectedMode>,
+    mut rules: ResMut<SelectedRules>,
+) {
+    if let Some(saved) = saved {
+        selected.0 = saved.level;
+        mode.0 = saved.mode;
+        rules.0 = saved.rules.clone();
+        commands.remove_resource::<SavedChoices>();
+    }
+    commands.remove_resource::<Playback>();
+    commands.remove_resource::<ScriptedInput>();
+    virtual_time.set_relative_speed(assist.game_speed);
+    virtual_time.unpause();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animations::player_animation_state::{PlayerState, PlayerStateKind};
+    use crate::engine::player::{Player, PlayerStatus};
+    use crate::engine::player_input::MoveDirection;
+    use crate::environment::platform_spawner::PlatformLayer;
+    use crate::replay::recording::Recording;
+    use crate::HeadlessPlugin;
+    use std::time::{Duration, Instant};
+
+    const ROUND_TICKS: u64 = 400;
+
+    enum Scripted {
+        Input(PlayerInputs),
+        Debug(DebugAction),
+    }
+
+    /// Position, state stack, hp, score and pot of the player.
+    type Snapshot = (Vec3, Vec<PlayerStateKind>, u32, u32, u32);
+
+    /// Loads the level, then runs the round to `ROUND_TICKS`, sending each scripted event
+    /// once its tick is reached.
+    fn play_round(app: &mut App, script: &[(u64, Scripted)]) -> Snapshot {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while *app.world().resource::<State<GameState>>().get() != GameState::InGame {
+            assert!(
+                Instant::now() < deadline,
+                "the level never finished loading"
+            );
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        let mut script = script.iter().peekable();
+        while app.world().resource::<SimulationTick>().0 < ROUND_TICKS {
+            let tick = app.world().resource::<SimulationTick>().0;
+            while let Some((_, event)) = script.next_if(|(at, _)| *at <= tick) {
+                match event {
+                    Scripted::Input(input) => {
+                        app.world_mut().send_event(input.clone());
+                    }
+                    Scripted::Debug(action) => {
+                        app.world_mut().send_event(action.clone());
+                    }
+                }
+            }
+            app.update();
+        }
+        let world = app.world_mut();
+        let (transform, state, status) = world
+            .query_filtered::<(&Transform, &PlayerState, &PlayerStatus), With<Player>>()
+            .single(world);
+        (
+            transform.translation,
+            state.0.clone(),
+            status.hp,
+            status.points,
+            status.pot,
+        )
+    }
+
+    #[test]
+    fn playback_reproduces_the_recorded_round() {
+        let mut recorded = App::new();
+        recorded.add_plugins(HeadlessPlugin::default());
+        let played = play_round(
+            &mut recorded,
+            &[
+                (10, Scripted::Debug(DebugAction::ForceExplosion(true))),
+                (
+                    20,
+                    Scripted::Debug(DebugAction::SpawnCrate {
+                        position: Vec2::new(0.0, 100.0),
+                        layer: PlatformLayer::Red,
+                    }),
+                ),
+                (150, Scripted::Input(PlayerInputs::Attack)),
+                (
+                    200,
+                    Scripted::Input(PlayerInputs::Walk(MoveDirection::Right)),
+                ),
+                (260, Scripted::Input(PlayerInputs::Jump)),
+                (
+                    300,
+                    Scripted::Input(PlayerInputs::WalkEnd(MoveDirection::Right)),
+                ),
+            ],
+        );
+        let replay = recorded.world().resource::<Recording>().0.clone();
+        assert_eq!(replay.inputs.len(), 4);
+        assert_eq!(replay.debug_actions.len(), 2);
+
+        // Started from Startup like `--replay`, as entering the main menu stops playback
+        let mut watched = App::new();
+        watched.add_plugins(HeadlessPlugin::default()).add_systems(
+            Startup,
+            move |mut commands: Commands| {
+                start_playback(&mut commands, replay.clone());
+            },
+        );
+        let replayed = play_round(&mut watched, &[]);
+
+        assert_eq!(played, replayed);
+    }
+}
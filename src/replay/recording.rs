@@ -0,0 +1,79 @@
+use super::playback::Playback;
+use super::replay_file::*;
+use crate::data::rule_set::SelectedRules;
+use crate::engine::assist::AssistSettings;
+use crate::engine::debug_action::DebugAction;
+use crate::engine::game_mode::SelectedMode;
+use crate::engine::player_input::PlayerInputs;
+use crate::engine::simulation::{GameRng, SimulationTick};
+use crate::environment::level_asset::{LevelCatalog, SelectedLevel};
+use bevy::prelude::*;
+
+/// The round currently being recorded. Saved to disk when the round ends.
+#[derive(Resource)]
+pub struct Recording(pub Replay);
+
+pub fn start_recording(
+    mut commands: Commands,
+    rng: Res<GameRng>,
+    catalog: Res<LevelCatalog>,
+    selected: Res<SelectedLevel>,
+    assist: Res<AssistSettings>,
+    mode: Res<SelectedMode>,
+    rules: Res<SelectedRules>,
+    playback: Option<Res<Playback>>,
+) {
+    if playback.is_some() {
+        return;
+    }
+    let level = catalog
+        .0
+        .get(selected.0)
+        .map_or_else(String::new, |entry| entry.path.to_string());
+    commands.insert_resource(Recording(Replay {
+        seed: rng.seed(),
+        level,
+        extra_hp: assist.extra_hp,
+        mode: mode.0,
+        rules: rules.0.clone(),
+        inputs: Vec::new(),
+        debug_actions: Vec::new(),
+    }));
+}
+
+pub fn record_inputs(
+    mut input_events: EventReader<PlayerInputs>,
+    tick: Res<SimulationTick>,
+    mut recording: ResMut<Recording>,
+) {
+    for input in input_events.read() {
+        recording.0.inputs.push(RecordedInput {
+            tick: tick.0,
+            input: input.clone(),
+        });
+    }
+}
+
+pub fn record_debug_actions(
+    mut actions: EventReader<DebugAction>,
+    tick: Res<SimulationTick>,
+    mut recording: ResMut<Recording>,
+) {
+    for action in actions.read() {
+        recording.0.debug_actions.push(RecordedDebugAction {
+            tick: tick.0,
+            action: action.clone(),
+        });
+    }
+}
+
+pub fn save_recording(mut commands: Commands, recording: Option<Res<Recording>>) {
+    let Some(recording) = recording else {
+        return;
+    };
+    match recording.0.save() {
+        Ok(path) => info!("Saved replay to {}", path.display()),
+        Err(error) => warn!("{error}"),
+    }
+    commands.remove_resource::<Recording>();
+}
@@ -0,0 +1,99 @@
+use crate::data::rule_set::RuleChoice;
+use crate::engine::debug_action::DebugAction;
+use crate::engine::game_mode::GameMode;
+use crate::engine::player_input::PlayerInputs;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Directory, relative to the working directory, where finished rounds are saved.
+pub const REPLAY_DIR: &str = "replays";
+pub const REPLAY_EXTENSION: &str = "replay.ron";
+
+/// Everything needed to play a round back: the RNG seed, the level and every input
+/// together with the simulation tick it was applied on.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Replay {
+    pub seed: u64,
+    pub level: String,
+    /// Assist extra hp the round was played with.
+    #[serde(default)]
+    pub extra_hp: u32,
+    #[serde(default)]
+    pub mode: GameMode,
+    /// Difficulty preset or custom rules the round was played with.
+    #[serde(default)]
+    pub rules: RuleChoice,
+    pub inputs: Vec<RecordedInput>,
+    /// Changes made from the debug inspector during the round.
+    #[serde(default)]
+    pub debug_actions: Vec<RecordedDebugAction>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecordedInput {
+    pub tick: u64,
+    pub input: PlayerInputs,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecordedDebugAction {
+    pub tick: u64,
+    pub action: DebugAction,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Could not access replay file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse replay file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Could not write replay file: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl Replay {
+    /// Tick of the last recorded input or debug action.
+    pub fn last_tick(&self) -> u64 {
+        let input = self.inputs.last().map_or(0, |recorded| recorded.tick);
+        let action = self
+            .debug_actions
+            .last()
+            .map_or(0, |recorded| recorded.tick);
+        input.max(action)
+    }
+
+    /// Writes the replay to `replays/round-<unix time>.replay.ron` and returns its path.
+    pub fn save(&self) -> Result<PathBuf, ReplayError> {
+        std::fs::create_dir_all(REPLAY_DIR)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let path = Path::new(REPLAY_DIR).join(format!("round-{timestamp}.{REPLAY_EXTENSION}"));
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    /// Most recently saved replay, if any.
+    pub fn latest() -> Option<PathBuf> {
+        std::fs::read_dir(REPLAY_DIR)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(REPLAY_EXTENSION)
+            })
+            .max_by_key(|entry| entry.metadata().and_then(|meta| meta.modified()).ok())
+            .map(|entry| entry.path())
+    }
+}
@@ -0,0 +1,48 @@
+use super::playback::*;
+use super::recording::*;
+use crate::animations::player_animation_state::player_movement_state;
+use crate::engine::assist::auto_attack;
+use crate::engine::simulation::seed_round;
+use crate::{GameState, InGameState};
+use bevy::prelude::*;
+
+/// Records every round to `replays/` and plays recordings back.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlaybackCommand>()
+            .add_systems(Startup, start_pending_replay)
+            .add_systems(
+                OnEnter(GameState::AssetLoading),
+                prepare_playback_round.run_if(resource_exists::<Playback>),
+            )
+            .add_systems(
+                OnEnter(GameState::InGame),
+                (
+                    start_recording.after(seed_round),
+                    rewind_playback.run_if(resource_exists::<Playback>),
+                ),
+            )
+            .add_systems(
+                FixedPreUpdate,
+                inject_replay_inputs
+                    .run_if(in_state(InGameState::Running).and(resource_exists::<Playback>)),
+            )
+            .add_systems(
+                FixedUpdate,
+                (record_inputs, record_debug_actions)
+                    .after(auto_attack)
+                    .before(player_movement_state)
+                    .run_if(in_state(InGameState::Running).and(resource_exists::<Recording>)),
+            )
+            .add_systems(
+                Update,
+                (handle_playback_commands, update_seek)
+                    .chain()
+                    .run_if(in_state(GameState::InGame).and(resource_exists::<Playback>)),
+            )
+            .add_systems(OnExit(GameState::InGame), save_recording)
+            .add_systems(OnEnter(GameState::MainMenu), stop_playback);
+    }
+}
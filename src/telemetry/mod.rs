@@ -0,0 +1,4 @@
+pub mod session_stats;
+pub mod telemetry_plugin;
+pub mod telemetry_record;
+pub mod telemetry_sink;
@@ -0,0 +1,254 @@
+use super::telemetry_record::*;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Width of one calibration bin, as a probability.
+pub const CALIBRATION_BIN_WIDTH: f32 = 0.1;
+/// Histogram buckets for crates struck before the first explosion; the last one also
+/// counts every longer streak.
+pub const STREAK_BUCKETS: usize = 10;
+
+/// Strikes whose predicted explosion chance fell into one bin, and how many exploded.
+#[derive(Debug, Default, Clone)]
+pub struct CalibrationBin {
+    pub strikes: u32,
+    pub explosions: u32,
+    pub predicted_total: f32,
+}
+
+impl CalibrationBin {
+    pub fn predicted(&self) -> Option<f32> {
+        (self.strikes > 0).then(|| self.predicted_total / self.strikes as f32)
+    }
+
+    pub fn observed(&self) -> Option<f32> {
+        (self.strikes > 0).then(|| self.explosions as f32 / self.strikes as f32)
+    }
+}
+
+/// Summary of one run of the game.
+#[derive(Debug, Clone)]
+pub struct SessionTrend {
+    pub rounds: u32,
+    pub mean_score: f32,
+    pub explosion_rate: f32,
+}
+
+/// Everything the Stats screen shows, computed from saved telemetry.
+#[derive(Debug, Default)]
+pub struct ClassroomStats {
+    pub calibration: Vec<CalibrationBin>,
+    /// `streaks[n]` is the number of rounds where `n` crates were struck safely before the
+    /// first explosion.
+    pub streaks: Vec<u32>,
+    pub rounds_without_explosion: u32,
+    /// Oldest first.
+    pub sessions: Vec<SessionTrend>,
+}
+
+impl ClassroomStats {
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    pub fn from_records(records: &[TelemetryRecord]) -> Self {
+        let bins = (1.0 / CALIBRATION_BIN_WIDTH).round() as usize;
+        let mut stats = Self {
+            calibration: vec![CalibrationBin::default(); bins],
+            streaks: vec![0; STREAK_BUCKETS],
+            ..Default::default()
+        };
+
+        // Records of one round stay in the order they were written
+        let mut rounds: BTreeMap<(u64, u32), Vec<&TelemetryRecord>> = BTreeMap::new();
+        for record in records {
+            rounds
+                .entry((record.session, record.round))
+                .or_default()
+                .push(record);
+        }
+
+        let mut sessions: BTreeMap<u64, (u32, u32, u32, u32)> = BTreeMap::new();
+        for ((session, _), events) in rounds.iter() {
+            let mut safe_strikes = 0;
+            let mut exploded = false;
+            let mut score = 0;
+            let mut strikes = 0;
+            let mut explosions = 0;
+            for record in events {
+                if let Some(round_score) = record.score {
+                    score = round_score;
+                }
+                let blew_up = match record.event {
+                    TelemetryEvent::CrateStruck => false,
+                    TelemetryEvent::CrateExploded => true,
+                    _ => continue,
+                };
+                strikes += 1;
+                if let Some(chance) = record.explosion_chance {
+                    let bin = ((chance / CALIBRATION_BIN_WIDTH) as usize).min(bins - 1);
+                    let bin = &mut stats.calibration[bin];
+                    bin.strikes += 1;
+                    bin.predicted_total += chance;
+                    if blew_up {
+                        bin.explosions += 1;
+                    }
+                }
+                if blew_up {
+                    explosions += 1;
+                    if !exploded {
+                        exploded = true;
+                        stats.streaks[safe_strikes.min(STREAK_BUCKETS - 1)] += 1;
+                    }
+                } else if !exploded {
+                    safe_strikes += 1;
+                }
+            }
+            if !exploded {
+                stats.rounds_without_explosion += 1;
+            }
+            let totals = sessions.entry(*session).or_default();
+            totals.0 += 1;
+            totals.1 += score;
+            totals.2 += strikes;
+            totals.3 += explosions;
+        }
+
+        stats.sessions = sessions
+            .into_values()
+            .map(|(rounds, score, strikes, explosions)| SessionTrend {
+                rounds,
+                mean_score: score as f32 / rounds as f32,
+                explosion_rate: if strikes > 0 {
+                    explosions as f32 / strikes as f32
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+        stats
+    }
+
+    /// Reads every session under `telemetry/`, in either format.
+    pub fn load() -> Self {
+        Self::from_records(&load_records(Path::new(TELEMETRY_DIR)))
+    }
+}
+
+/// Parses all `*.jsonl` and `*.csv` files in `dir`, skipping lines that don't parse.
+pub fn load_records(dir: &Path) -> Vec<TelemetryRecord> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut records = Vec::new();
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        let format = match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) => match ext.parse::<TelemetryFormat>() {
+                Ok(format) => format,
+                Err(_) => continue,
+            },
+            None => continue,
+        };
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        records.extend(contents.lines().filter_map(|line| match format {
+            TelemetryFormat::JsonLines => serde_json::from_str(line).ok(),
+            TelemetryFormat::Csv => TelemetryRecord::from_csv_row(line),
+        }));
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strike(session: u64, round: u32, exploded: bool, chance: f32) -> TelemetryRecord {
+        let event = if exploded {
+            TelemetryEvent::CrateExploded
+        } else {
+            TelemetryEvent::CrateStruck
+        };
+        let mut record = TelemetryRecord::new(session, round, 0, event);
+        record.explosion_chance = Some(chance);
+        record
+    }
+
+    fn round_end(session: u64, round: u32, score: u32) -> TelemetryRecord {
+        let mut record = TelemetryRecord::new(session, round, 0, TelemetryEvent::RoundEnded);
+        record.score = Some(score);
+        record
+    }
+
+    fn sample_stats() -> ClassroomStats {
+        ClassroomStats::from_records(&[
+            // A later session listed first, to check the ordering
+            strike(200, 1, true, 0.55),
+            round_end(200, 1, 0),
+            strike(100, 1, false, 0.05),
+            strike(100, 1, false, 0.15),
+            strike(100, 1, true, 0.25),
+            strike(100, 1, false, 0.05),
+            round_end(100, 1, 12),
+            strike(100, 2, false, 0.95),
+            round_end(100, 2, 4),
+        ])
+    }
+
+    #[test]
+    fn strikes_fall_into_calibration_bins() {
+        let stats = sample_stats();
+        let counts: Vec<(u32, u32)> = stats
+            .calibration
+            .iter()
+            .map(|bin| (bin.strikes, bin.explosions))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                (2, 0),
+                (1, 0),
+                (1, 1),
+                (0, 0),
+                (0, 0),
+                (1, 1),
+                (0, 0),
+                (0, 0),
+                (0, 0),
+                (1, 0)
+            ]
+        );
+        assert_eq!(stats.calibration[0].predicted(), Some(0.05));
+        assert_eq!(stats.calibration[2].observed(), Some(1.0));
+        assert_eq!(stats.calibration[3].predicted(), None);
+    }
+
+    #[test]
+    fn streaks_count_safe_strikes_before_the_first_explosion() {
+        let stats = sample_stats();
+        assert_eq!(stats.streaks[0], 1);
+        assert_eq!(stats.streaks[2], 1);
+        assert_eq!(stats.streaks.iter().sum::<u32>(), 2);
+        assert_eq!(stats.rounds_without_explosion, 1);
+    }
+
+    #[test]
+    fn sessions_are_summarised_oldest_first() {
+        let stats = sample_stats();
+        let sessions: Vec<(u32, f32, f32)> = stats
+            .sessions
+            .iter()
+            .map(|trend| (trend.rounds, trend.mean_score, trend.explosion_rate))
+            .collect();
+        assert_eq!(sessions, [(2, 8.0, 0.2), (1, 0.0, 1.0)]);
+    }
+
+    #[test]
+    fn no_records_means_no_stats() {
+        assert!(ClassroomStats::from_records(&[]).is_empty());
+    }
+}
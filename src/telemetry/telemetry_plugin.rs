@@ -0,0 +1,31 @@
+use super::telemetry_sink::*;
+use crate::engine::simulation::seed_round;
+use crate::GameState;
+use bevy::prelude::*;
+
+/// Opt-in recording of round events to `telemetry/`, for teachers and for balancing the
+/// explosion curve. Enable it by setting `TelemetrySettings::format`, e.g. with
+/// `--telemetry jsonl` or `--telemetry csv`.
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TelemetrySettings>()
+            .add_systems(PostStartup, open_telemetry_sink)
+            .add_systems(
+                OnEnter(GameState::InGame),
+                record_round_start
+                    .after(seed_round)
+                    .run_if(resource_exists::<TelemetrySink>),
+            )
+            .add_systems(
+                Update,
+                (record_crate_spawns, record_gameplay_events)
+                    .run_if(in_state(GameState::InGame).and(resource_exists::<TelemetrySink>)),
+            )
+            .add_systems(
+                OnExit(GameState::InGame),
+                record_round_end.run_if(resource_exists::<TelemetrySink>),
+            );
+    }
+}
@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Directory, relative to the working directory, that telemetry sessions are written to.
+pub const TELEMETRY_DIR: &str = "telemetry";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryFormat {
+    /// One JSON object per line, `*.jsonl`.
+    JsonLines,
+    /// Comma separated values with a header row, `*.csv`.
+    Csv,
+}
+
+impl TelemetryFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            TelemetryFormat::JsonLines => "jsonl",
+            TelemetryFormat::Csv => "csv",
+        }
+    }
+}
+
+impl FromStr for TelemetryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "jsonl" | "json" => Ok(TelemetryFormat::JsonLines),
+            "csv" => Ok(TelemetryFormat::Csv),
+            other => Err(format!(
+                "Unknown telemetry format '{other}', expected jsonl or csv"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TelemetryEvent {
+    RoundStarted,
+    CrateSpawned,
+    CrateStruck,
+    CrateExploded,
+    PlayerDamaged,
+    PotBanked,
+    PotLost,
+    /// A Prediction mode call, settled by the next struck crate.
+    Prediction,
+    RoundEnded,
+}
+
+impl FromStr for TelemetryEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RoundStarted" => Ok(TelemetryEvent::RoundStarted),
+            "CrateSpawned" => Ok(TelemetryEvent::CrateSpawned),
+            "CrateStruck" => Ok(TelemetryEvent::CrateStruck),
+            "CrateExploded" => Ok(TelemetryEvent::CrateExploded),
+            "PlayerDamaged" => Ok(TelemetryEvent::PlayerDamaged),
+            "PotBanked" => Ok(TelemetryEvent::PotBanked),
+            "PotLost" => Ok(TelemetryEvent::PotLost),
+            "Prediction" => Ok(TelemetryEvent::Prediction),
+            "RoundEnded" => Ok(TelemetryEvent::RoundEnded),
+            other => Err(format!("Unknown telemetry event '{other}'")),
+        }
+    }
+}
+
+impl fmt::Display for TelemetryEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// One row of telemetry. Kept flat so the same record works as a JSON line and a CSV
+/// row; fields that don't apply to an event are left empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryRecord {
+    /// Unix time the session started, shared by every record of one run of the game.
+    pub session: u64,
+    pub round: u32,
+    /// Simulation tick within the round.
+    pub tick: u64,
+    pub event: TelemetryEvent,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub level: Option<String>,
+    #[serde(default)]
+    pub crate_index: Option<u32>,
+    /// The explosion probability the game predicted for the crate involved.
+    #[serde(default)]
+    pub explosion_chance: Option<f32>,
+    /// Points scored, banked or lost, or damage taken by this event.
+    #[serde(default)]
+    pub amount: Option<u32>,
+    #[serde(default)]
+    pub hp: Option<u32>,
+    #[serde(default)]
+    pub score: Option<u32>,
+    /// For a prediction, whether the player called an explosion.
+    #[serde(default)]
+    pub called_explosion: Option<bool>,
+    /// For a prediction, whether the call matched the crate.
+    #[serde(default)]
+    pub correct: Option<bool>,
+}
+
+pub const CSV_HEADER: &str =
+    "session,round,tick,event,seed,level,crate_index,explosion_chance,amount,hp,score,called_explosion,correct";
+
+impl TelemetryRecord {
+    pub fn new(session: u64, round: u32, tick: u64, event: TelemetryEvent) -> Self {
+        Self {
+            session,
+            round,
+            tick,
+            event,
+            seed: None,
+            level: None,
+            crate_index: None,
+            explosion_chance: None,
+            amount: None,
+            hp: None,
+            score: None,
+            called_explosion: None,
+            correct: None,
+        }
+    }
+
+    pub fn to_csv_row(&self) -> String {
+        fn field<T: ToString>(value: &Option<T>) -> String {
+            value.as_ref().map(ToString::to_string).unwrap_or_default()
+        }
+        // Level names are the only free text; keep them from breaking the columns
+        let level = self.level.as_deref().unwrap_or_default().replace(',', " ");
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.session,
+            self.round,
+            self.tick,
+            self.event,
+            field(&self.seed),
+            level,
+            field(&self.crate_index),
+            field(&self.explosion_chance),
+            field(&self.amount),
+            field(&self.hp),
+            field(&self.score),
+            field(&self.called_explosion),
+            field(&self.correct),
+        )
+    }
+
+    /// Parses a row written by `to_csv_row`. Returns `None` for the header and bad rows.
+    pub fn from_csv_row(row: &str) -> Option<Self> {
+        fn field<T: FromStr>(value: &str) -> Option<Option<T>> {
+            if value.is_empty() {
+                Some(None)
+            } else {
+                value.parse().ok().map(Some)
+            }
+        }
+        let columns: Vec<&str> = row.trim().split(',').collect();
+        let [session, round, tick, event, seed, level, crate_index, explosion_chance, amount, hp, score, called_explosion, correct] =
+            columns.as_slice()
+        else {
+            return None;
+        };
+        Some(Self {
+            session: session.parse().ok()?,
+            round: round.parse().ok()?,
+            tick: tick.parse().ok()?,
+            event: event.parse().ok()?,
+            seed: field(seed)?,
+            level: (!level.is_empty()).then(|| level.to_string()),
+            crate_index: field(crate_index)?,
+            explosion_chance: field(explosion_chance)?,
+            amount: field(amount)?,
+            hp: field(hp)?,
+            score: field(score)?,
+            called_explosion: field(called_explosion)?,
+            correct: field(correct)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_row_matches_the_header() {
+        let record = TelemetryRecord::new(1, 1, 0, TelemetryEvent::RoundEnded);
+        assert_eq!(
+            record.to_csv_row().split(',').count(),
+            CSV_HEADER.split(',').count()
+        );
+    }
+
+    #[test]
+    fn csv_row_leaves_missing_fields_empty() {
+        let mut record = TelemetryRecord::new(1700000000, 3, 420, TelemetryEvent::CrateSpawned);
+        record.crate_index = Some(7);
+        record.explosion_chance = Some(0.25);
+
+        assert_eq!(
+            record.to_csv_row(),
+            "1700000000,3,420,CrateSpawned,,,7,0.25,,,,,"
+        );
+    }
+
+    #[test]
+    fn commas_in_level_names_keep_the_columns() {
+        let mut record = TelemetryRecord::new(1, 1, 0, TelemetryEvent::RoundStarted);
+        record.seed = Some(99);
+        record.level = Some("Hall, upper".to_string());
+
+        assert_eq!(
+            record.to_csv_row(),
+            "1,1,0,RoundStarted,99,Hall  upper,,,,,,,"
+        );
+    }
+
+    #[test]
+    fn csv_row_round_trips() {
+        let mut record = TelemetryRecord::new(1700000000, 3, 420, TelemetryEvent::CrateStruck);
+        record.seed = Some(99);
+        record.level = Some("Classic".to_string());
+        record.crate_index = Some(7);
+        record.explosion_chance = Some(0.25);
+        record.amount = Some(4);
+
+        let parsed = TelemetryRecord::from_csv_row(&record.to_csv_row()).unwrap();
+        assert_eq!(parsed.session, 1700000000);
+        assert_eq!(parsed.round, 3);
+        assert_eq!(parsed.tick, 420);
+        assert_eq!(parsed.event, TelemetryEvent::CrateStruck);
+        assert_eq!(parsed.seed, Some(99));
+        assert_eq!(parsed.level.as_deref(), Some("Classic"));
+        assert_eq!(parsed.crate_index, Some(7));
+        assert_eq!(parsed.explosion_chance, Some(0.25));
+        assert_eq!(parsed.amount, Some(4));
+        assert_eq!(parsed.hp, None);
+        assert_eq!(parsed.score, None);
+        assert_eq!(parsed.called_explosion, None);
+    }
+
+    #[test]
+    fn header_and_bad_rows_are_skipped() {
+        assert!(TelemetryRecord::from_csv_row(CSV_HEADER).is_none());
+        assert!(TelemetryRecord::from_csv_row("").is_none());
+        assert!(TelemetryRecord::from_csv_row("1,1,0,RoundStarted").is_none());
+        assert!(TelemetryRecord::from_csv_row("1,1,0,Teleported,,,,,,,,,").is_none());
+        assert!(TelemetryRecord::from_csv_row("1,1,0,CrateStruck,,,,lots,,,,,").is_none());
+    }
+
+    #[test]
+    fn prediction_records_the_call_and_outcome() {
+        let mut record = TelemetryRecord::new(1, 2, 300, TelemetryEvent::Prediction);
+        record.crate_index = Some(4);
+        record.explosion_chance = Some(0.5);
+        record.called_explosion = Some(true);
+        record.correct = Some(false);
+
+        assert_eq!(
+            record.to_csv_row(),
+            "1,2,300,Prediction,,,4,0.5,,,,true,false"
+        );
+        let parsed = TelemetryRecord::from_csv_row(&record.to_csv_row()).unwrap();
+        assert_eq!(parsed.event, TelemetryEvent::Prediction);
+        assert_eq!(parsed.called_explosion, Some(true));
+        assert_eq!(parsed.correct, Some(false));
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains(r#""event":"Prediction""#), "{json}");
+        assert!(json.contains(r#""called_explosion":true"#), "{json}");
+        assert!(json.contains(r#""correct":false"#), "{json}");
+    }
+}
@@ -0,0 +1,217 @@
+use super::telemetry_record::*;
+use crate::engine::gameplay_events::GameplayEvent;
+use crate::engine::player::{Player, PlayerStatus};
+use crate::engine::simulation::{GameRng, SimulationTick};
+use crate::environment::crate_spawner::CrateRisk;
+use crate::environment::level_asset::{LevelCatalog, SelectedLevel};
+use bevy::prelude::*;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Whether, and in which format, round events are written to disk. Off by default.
+#[derive(Resource, Default)]
+pub struct TelemetrySettings {
+    pub format: Option<TelemetryFormat>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum TelemetryError {
+    #[error("Could not write telemetry: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not encode telemetry: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Open telemetry file for this run of the game.
+#[derive(Resource)]
+pub struct TelemetrySink {
+    format: TelemetryFormat,
+    writer: BufWriter<File>,
+    path: PathBuf,
+    session: u64,
+    round: u32,
+}
+
+impl TelemetrySink {
+    pub fn create(format: TelemetryFormat) -> Result<Self, TelemetryError> {
+        std::fs::create_dir_all(TELEMETRY_DIR)?;
+        let session = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let path =
+            Path::new(TELEMETRY_DIR).join(format!("session-{session}.{}", format.extension()));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        if format == TelemetryFormat::Csv {
+            writeln!(writer, "{CSV_HEADER}")?;
+        }
+        Ok(Self {
+            format,
+            writer,
+            path,
+            session,
+            round: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, tick: u64, event: TelemetryEvent) -> TelemetryRecord {
+        TelemetryRecord::new(self.session, self.round, tick, event)
+    }
+
+    pub fn write(&mut self, record: &TelemetryRecord) -> Result<(), TelemetryError> {
+        match self.format {
+            TelemetryFormat::JsonLines => {
+                serde_json::to_writer(&mut self.writer, record)?;
+                writeln!(self.writer)?;
+            }
+            TelemetryFormat::Csv => writeln!(self.writer, "{}", record.to_csv_row())?,
+        }
+        Ok(())
+    }
+}
+
+/// Writes `record`, or reports the failure and stops recording for the rest of the run.
+fn write_or_close(commands: &mut Commands, sink: &mut TelemetrySink, record: TelemetryRecord) {
+    if let Err(error) = sink.write(&record) {
+        warn!("{error}");
+        commands.remove_resource::<TelemetrySink>();
+    }
+}
+
+pub fn open_telemetry_sink(mut commands: Commands, settings: Res<TelemetrySettings>) {
+    let Some(format) = settings.format else {
+        return;
+    };
+    match TelemetrySink::create(format) {
+        Ok(sink) => {
+            info!("Writing telemetry to {}", sink.path().display());
+            commands.insert_resource(sink);
+        }
+        Err(error) => warn!("{error}"),
+    }
+}
+
+pub fn record_round_start(
+    mut commands: Commands,
+    mut sink: ResMut<TelemetrySink>,
+    rng: Res<GameRng>,
+    catalog: Res<LevelCatalog>,
+    selected: Res<SelectedLevel>,
+) {
+    sink.round += 1;
+    let mut record = sink.record(0, TelemetryEvent::RoundStarted);
+    record.seed = Some(rng.seed());
+    record.level = catalog
+        .0
+        .get(selected.0)
+        .map(|entry| entry.name.to_string());
+    write_or_close(&mut commands, &mut sink, record);
+}
+
+pub fn record_crate_spawns(
+    mut commands: Commands,
+    mut sink: ResMut<TelemetrySink>,
+    tick: Res<SimulationTick>,
+    spawned: Query<&CrateRisk, Added<CrateRisk>>,
+) {
+    for risk in spawned.iter() {
+        let mut record = sink.record(tick.0, TelemetryEvent::CrateSpawned);
+        record.crate_index = Some(risk.index);
+        record.explosion_chance = Some(risk.explosion_chance);
+        write_or_close(&mut commands, &mut sink, record);
+    }
+}
+
+pub fn record_gameplay_events(
+    mut commands: Commands,
+    mut sink: ResMut<TelemetrySink>,
+    tick: Res<SimulationTick>,
+    mut gameplay_events: EventReader<GameplayEvent>,
+    players: Query<&PlayerStatus, With<Player>>,
+) {
+    let status = players.iter().next();
+    for event in gameplay_events.read() {
+        let mut record = match event {
+            GameplayEvent::CrateStruck {
+                crate_index,
+                explosion_chance,
+                points,
+                ..
+            } => {
+                let mut record = sink.record(tick.0, TelemetryEvent::CrateStruck);
+                record.crate_index = Some(*crate_index);
+                record.explosion_chance = Some(*explosion_chance);
+                record.amount = Some(*points);
+                record
+            }
+            GameplayEvent::CrateExploded {
+                crate_index,
+                explosion_chance,
+                ..
+            } => {
+                let mut record = sink.record(tick.0, TelemetryEvent::CrateExploded);
+                record.crate_index = Some(*crate_index);
+                record.explosion_chance = Some(*explosion_chance);
+                record
+            }
+            GameplayEvent::PlayerDamaged { amount, .. } => {
+                let mut record = sink.record(tick.0, TelemetryEvent::PlayerDamaged);
+                record.amount = Some(*amount);
+                record
+            }
+            GameplayEvent::PotBanked { amount, .. } => {
+                let mut record = sink.record(tick.0, TelemetryEvent::PotBanked);
+                record.amount = Some(*amount);
+                record
+            }
+            GameplayEvent::PotLost { amount, .. } => {
+                let mut record = sink.record(tick.0, TelemetryEvent::PotLost);
+                record.amount = Some(*amount);
+                record
+            }
+            GameplayEvent::PredictionSettled {
+                called_explosion,
+                correct,
+                crate_index,
+                explosion_chance,
+                ..
+            } => {
+                let mut record = sink.record(tick.0, TelemetryEvent::Prediction);
+                record.crate_index = Some(*crate_index);
+                record.explosion_chance = Some(*explosion_chance);
+                record.called_explosion = Some(*called_explosion);
+                record.correct = Some(*correct);
+                record
+            }
+            _ => continue,
+        };
+        record.hp = status.map(|status| status.hp);
+        record.score = status.map(|status| status.points);
+        write_or_close(&mut commands, &mut sink, record);
+    }
+}
+
+pub fn record_round_end(
+    mut commands: Commands,
+    mut sink: ResMut<TelemetrySink>,
+    tick: Res<SimulationTick>,
+    players: Query<&PlayerStatus, With<Player>>,
+) {
+    let mut record = sink.record(tick.0, TelemetryEvent::RoundEnded);
+    if let Some(status) = players.iter().next() {
+        record.hp = Some(status.hp);
+        record.score = Some(status.points);
+    }
+    write_or_close(&mut commands, &mut sink, record);
+    // Make finished rounds readable even if the game is killed later
+    if let Err(error) = sink.writer.flush() {
+        warn!("Could not flush telemetry: {error}");
+    }
+}
@@ -0,0 +1,4 @@
+pub mod odds;
+pub mod tutor_hints;
+pub mod tutor_plugin;
+pub mod tutor_settings;
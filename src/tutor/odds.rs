@@ -0,0 +1,246 @@
+use super::tutor_settings::AgeGroup;
+use crate::localization::string_table::StringTable;
+
+/// Denominators tried, in order, when writing a chance as "n in d".
+const DENOMINATORS: [u32; 3] = [10, 20, 100];
+/// Boxes in an `AgeGroup::Early` pictogram.
+pub const PICTOGRAM_SIZE: u32 = 10;
+
+/// A probability written as "`numerator` in `denominator`".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fraction {
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+impl Fraction {
+    /// The smallest of 10, 20 or 100 that shows the chance as neither impossible nor
+    /// certain.
+    pub fn approximate(probability: f32) -> Self {
+        let certain = probability <= 0.0 || probability >= 1.0;
+        DENOMINATORS
+            .iter()
+            .map(|&denominator| Fraction::rounded(probability, denominator))
+            .find(|fraction| {
+                certain || (fraction.numerator > 0 && fraction.numerator < fraction.denominator)
+            })
+            // Too close to 0 or 1 to show even out of 100, but it can still go either way
+            .unwrap_or_else(|| Fraction::rounded(probability, 100).clamped(probability))
+    }
+
+    /// Boxes out of `PICTOGRAM_SIZE` to colour in.
+    pub fn pictogram(probability: f32) -> Self {
+        Fraction::rounded(probability, PICTOGRAM_SIZE).clamped(probability)
+    }
+
+    fn rounded(probability: f32, denominator: u32) -> Self {
+        Fraction {
+            numerator: (probability * denominator as f32).round() as u32,
+            denominator,
+        }
+    }
+
+    /// Keeps a chance that is neither 0 nor 1 from being shown as one.
+    fn clamped(self, probability: f32) -> Self {
+        if probability <= 0.0 || probability >= 1.0 {
+            return self;
+        }
+        Fraction {
+            numerator: self.numerator.clamp(1, self.denominator - 1),
+            ..self
+        }
+    }
+}
+
+/// Short explanation of a crate's explosion chance for the given age group.
+pub fn describe_odds(strings: &StringTable, age_group: AgeGroup, probability: f32) -> String {
+    match age_group {
+        AgeGroup::Early => {
+            let fraction = Fraction::pictogram(probability);
+            strings.format(
+                "tutor.odds.pictogram",
+                &[
+                    ("n", fraction.numerator.to_string()),
+                    ("d", fraction.denominator.to_string()),
+                ],
+            )
+        }
+        AgeGroup::Middle => {
+            let fraction = Fraction::approximate(probability);
+            strings.format(
+                "tutor.odds.fraction",
+                &[
+                    ("n", fraction.numerator.to_string()),
+                    ("d", fraction.denominator.to_string()),
+                ],
+            )
+        }
+        AgeGroup::Older => strings.format(
+            "tutor.odds.percent",
+            &[("percent", format!("{:.0}", probability * 100.0))],
+        ),
+    }
+}
+
+/// Average outcome of the two choices the player has with a pot at risk.
+#[derive(Debug, Clone, Copy)]
+pub struct ExpectedValues {
+    /// Pot after one more strike, averaged over exploding and not exploding.
+    pub strike: f32,
+    /// Banking keeps the pot as it is.
+    pub bank: f32,
+}
+
+impl ExpectedValues {
+    /// A safe hit adds `streak + 1` to the pot; an explosion loses all of it.
+    pub fn new(pot: u32, streak: u32, explosion_chance: f32) -> Self {
+        Self {
+            strike: (1.0 - explosion_chance) * (pot + streak + 1) as f32,
+            bank: pot as f32,
+        }
+    }
+
+    pub fn strike_is_better(&self) -> bool {
+        self.strike > self.bank
+    }
+}
+
+/// Lines comparing striking with banking, simplified for younger players.
+pub fn describe_expected_values(
+    strings: &StringTable,
+    age_group: AgeGroup,
+    values: ExpectedValues,
+) -> String {
+    if age_group == AgeGroup::Early {
+        let key = if values.strike_is_better() {
+            "tutor.ev.simple_strike"
+        } else {
+            "tutor.ev.simple_bank"
+        };
+        return strings.get(key).to_string();
+    }
+    let decimals = if age_group == AgeGroup::Older { 1 } else { 0 };
+    let strike = strings.format(
+        "tutor.ev.strike",
+        &[("value", format!("{:.*}", decimals, values.strike))],
+    );
+    let bank = strings.format("tutor.ev.bank", &[("value", format!("{:.0}", values.bank))]);
+    let verdict = strings.get(if values.strike_is_better() {
+        "tutor.ev.better_strike"
+    } else {
+        "tutor.ev.better_bank"
+    });
+    format!("{strike}\n{bank}\n{verdict}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings() -> StringTable {
+        let mut table = StringTable::default();
+        for (key, text) in [
+            ("tutor.odds.pictogram", "{n} of {d} boxes"),
+            ("tutor.odds.fraction", "{n} in {d}"),
+            ("tutor.odds.percent", "{percent}%"),
+        ] {
+            table.strings.insert(key.to_string(), text.to_string());
+        }
+        table
+    }
+
+    #[test]
+    fn fraction_picks_the_smallest_denominator_that_shows_the_chance() {
+        assert_eq!(
+            Fraction::approximate(0.3),
+            Fraction {
+                numerator: 3,
+                denominator: 10
+            }
+        );
+        assert_eq!(
+            Fraction::approximate(0.04),
+            Fraction {
+                numerator: 1,
+                denominator: 20
+            }
+        );
+        assert_eq!(
+            Fraction::approximate(0.02),
+            Fraction {
+                numerator: 2,
+                denominator: 100
+            }
+        );
+    }
+
+    #[test]
+    fn possible_chances_never_round_to_zero_or_certain() {
+        assert_eq!(
+            Fraction::approximate(0.001),
+            Fraction {
+                numerator: 1,
+                denominator: 100
+            }
+        );
+        assert_eq!(
+            Fraction::approximate(0.999),
+            Fraction {
+                numerator: 99,
+                denominator: 100
+            }
+        );
+        assert_eq!(
+            Fraction::approximate(0.97),
+            Fraction {
+                numerator: 19,
+                denominator: 20
+            }
+        );
+        assert_eq!(Fraction::approximate(0.0).numerator, 0);
+        assert_eq!(Fraction::approximate(1.0).numerator, 10);
+    }
+
+    #[test]
+    fn pictogram_rounds_to_ten_boxes() {
+        assert_eq!(
+            Fraction::pictogram(0.26),
+            Fraction {
+                numerator: 3,
+                denominator: PICTOGRAM_SIZE
+            }
+        );
+    }
+
+    #[test]
+    fn pictogram_keeps_a_box_for_each_outcome() {
+        assert_eq!(Fraction::pictogram(0.02).numerator, 1);
+        assert_eq!(Fraction::pictogram(0.98).numerator, PICTOGRAM_SIZE - 1);
+        assert_eq!(Fraction::pictogram(0.0).numerator, 0);
+        assert_eq!(Fraction::pictogram(1.0).numerator, PICTOGRAM_SIZE);
+    }
+
+    #[test]
+    fn expected_values_compare_striking_with_banking() {
+        // 10% to lose a pot of 9, 90% to grow it to 9 + 2 + 1
+        let values = ExpectedValues::new(9, 2, 0.1);
+        assert!((values.strike - 10.8).abs() < 1e-4);
+        assert_eq!(values.bank, 9.0);
+        assert!(values.strike_is_better());
+
+        let values = ExpectedValues::new(9, 2, 0.5);
+        assert!((values.strike - 6.0).abs() < 1e-4);
+        assert!(!values.strike_is_better());
+    }
+
+    #[test]
+    fn odds_are_described_per_age_group() {
+        let strings = strings();
+        assert_eq!(
+            describe_odds(&strings, AgeGroup::Early, 0.3),
+            "3 of 10 boxes"
+        );
+        assert_eq!(describe_odds(&strings, AgeGroup::Middle, 0.04), "1 in 20");
+        assert_eq!(describe_odds(&strings, AgeGroup::Older, 0.123), "12%");
+    }
+}
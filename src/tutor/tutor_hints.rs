@@ -0,0 +1,220 @@
+use super::odds::*;
+use super::tutor_settings::*;
+use crate::consts;
+use crate::engine::player::{Player, PlayerStatus};
+use crate::environment::crate_spawner::{CrateRisk, WackyCrate};
+use crate::game_ui::ui_theme::UiTheme;
+use crate::game_ui::widgets::panel;
+use crate::localization::string_table::StringTable;
+use bevy::prelude::*;
+
+/// Crates further than this from the player get no hint.
+const HINT_RANGE: f32 = 300.0;
+/// Where the hint sits relative to its crate.
+const HINT_OFFSET: Vec3 = Vec3::new(0.0, 60.0, 6.0);
+const PICTOGRAM_BOX: f32 = 8.0;
+
+/// The crate the tutor is currently explaining: the nearest one to the player.
+#[derive(Resource, Default)]
+pub struct TutorTarget(pub Option<Entity>);
+
+/// Odds label floating above `target`.
+#[derive(Component)]
+pub struct CrateHint {
+    pub target: Entity,
+    pub age_group: AgeGroup,
+    pub explosion_chance: f32,
+}
+
+#[derive(Component)]
+pub struct TutorPanelText;
+
+#[derive(Component)]
+pub struct OnTutorPanel;
+
+pub fn find_tutor_target(
+    mut target: ResMut<TutorTarget>,
+    players: Query<&Transform, With<Player>>,
+    crates: Query<(Entity, &Transform), (With<WackyCrate>, With<CrateRisk>)>,
+) {
+    let Some(player_xf) = players.iter().next() else {
+        target.0 = None;
+        return;
+    };
+    target.0 = crates
+        .iter()
+        .map(|(entity, crate_xf)| {
+            let distance = crate_xf
+                .translation
+                .truncate()
+                .distance(player_xf.translation.truncate());
+            (entity, distance)
+        })
+        .filter(|(_, distance)| *distance <= HINT_RANGE)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity);
+}
+
+fn spawn_crate_hint(
+    commands: &mut Commands,
+    strings: &StringTable,
+    target: Entity,
+    age_group: AgeGroup,
+    explosion_chance: f32,
+    position: Vec3,
+) {
+    commands
+        .spawn((
+            CrateHint {
+                target,
+                age_group,
+                explosion_chance,
+            },
+            Transform::from_translation(position + HINT_OFFSET),
+            Visibility::default(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2d::new(describe_odds(strings, age_group, explosion_chance)),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(consts::TEXT_COLOR),
+                Transform::from_xyz(0.0, 2.0 * PICTOGRAM_BOX, 0.0),
+            ));
+            if age_group != AgeGroup::Early {
+                return;
+            }
+            let exploding = Fraction::pictogram(explosion_chance).numerator;
+            for index in 0..PICTOGRAM_SIZE {
+                let color = if index < exploding {
+                    consts::PICTOGRAM_EXPLODE
+                } else {
+                    consts::PICTOGRAM_SAFE
+                };
+                let x = (index as f32 - (PICTOGRAM_SIZE - 1) as f32 / 2.0) * PICTOGRAM_BOX * 1.25;
+                parent.spawn((
+                    Sprite {
+                        color,
+                        custom_size: Some(Vec2::splat(PICTOGRAM_BOX)),
+                        ..default()
+                    },
+                    Transform::from_xyz(x, 0.0, 0.0),
+                ));
+            }
+        });
+}
+
+/// Keeps a single odds label above the tutor's target crate, rebuilding it when the
+/// target, its chance, the age group or the string table changes.
+pub fn update_crate_hint(
+    mut commands: Commands,
+    settings: Res<TutorSettings>,
+    strings: Res<StringTable>,
+    target: Res<TutorTarget>,
+    crates: Query<(&Transform, &CrateRisk), With<WackyCrate>>,
+    mut hints: Query<(Entity, &CrateHint, &mut Transform), Without<WackyCrate>>,
+) {
+    let target = target
+        .0
+        .and_then(|entity| crates.get(entity).ok().map(|found| (entity, found)));
+    let mut current = None;
+    for (hint_entity, hint, mut hint_xf) in hints.iter_mut() {
+        let still_valid = target.is_some_and(|(entity, (_, risk))| {
+            hint.target == entity
+                && hint.age_group == settings.age_group
+                && hint.explosion_chance == risk.explosion_chance
+        });
+        if still_valid && !strings.is_changed() {
+            if let Some((_, (crate_xf, _))) = target {
+                hint_xf.translation = crate_xf.translation + HINT_OFFSET;
+            }
+            current = Some(hint_entity);
+        } else {
+            commands.entity(hint_entity).despawn_recursive();
+        }
+    }
+    if current.is_some() {
+        return;
+    }
+    if let Some((entity, (crate_xf, risk))) = target {
+        spawn_crate_hint(
+            &mut commands,
+            &strings,
+            entity,
+            settings.age_group,
+            risk.explosion_chance,
+            crate_xf.translation,
+        );
+    }
+}
+
+pub fn despawn_crate_hints(mut commands: Commands, hints: Query<Entity, With<CrateHint>>) {
+    for entity in hints.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Panel along the bottom of the screen comparing striking with banking.
+pub fn setup_tutor_panel(mut commands: Commands, theme: Res<UiTheme>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(80.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnTutorPanel,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::axes(Val::Px(24.0), Val::Px(12.0)),
+                        ..default()
+                    },
+                    panel(&theme),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        theme.text(22.0),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        TutorPanelText,
+                    ));
+                });
+        });
+}
+
+pub fn update_tutor_panel(
+    settings: Res<TutorSettings>,
+    strings: Res<StringTable>,
+    target: Res<TutorTarget>,
+    crates: Query<&CrateRisk>,
+    players: Query<&PlayerStatus, With<Player>>,
+    mut texts: Query<&mut Text, With<TutorPanelText>>,
+) {
+    let risk = target.0.and_then(|entity| crates.get(entity).ok());
+    let text = match (settings.enabled, risk, players.iter().next()) {
+        (true, Some(risk), Some(status)) => describe_expected_values(
+            &strings,
+            settings.age_group,
+            ExpectedValues::new(status.pot, status.streak, risk.explosion_chance),
+        ),
+        _ => String::new(),
+    };
+    for mut panel_text in texts.iter_mut() {
+        if panel_text.0 != text {
+            panel_text.0 = text.clone();
+        }
+    }
+}
+
+pub fn cleanup_tutor_panel(mut commands: Commands, query: Query<Entity, With<OnTutorPanel>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
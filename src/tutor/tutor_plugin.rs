@@ -0,0 +1,35 @@
+use super::tutor_hints::*;
+use super::tutor_settings::*;
+use crate::GameState;
+use bevy::prelude::*;
+
+fn tutor_enabled(settings: Res<TutorSettings>) -> bool {
+    settings.enabled
+}
+
+/// Educational hints: the odds of the crate nearest the player and the expected value of
+/// striking it versus banking. Off until enabled in Settings.
+pub struct TutorPlugin;
+
+impl Plugin for TutorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TutorSettings>()
+            .init_resource::<TutorTarget>()
+            .add_systems(OnEnter(GameState::InGame), setup_tutor_panel)
+            .add_systems(
+                Update,
+                (
+                    find_tutor_target,
+                    update_crate_hint.run_if(tutor_enabled),
+                    despawn_crate_hints.run_if(not(tutor_enabled)),
+                    update_tutor_panel,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                OnExit(GameState::InGame),
+                (despawn_crate_hints, cleanup_tutor_panel),
+            );
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+/// How the tutor presents a probability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AgeGroup {
+    /// Ages 5-7: a row of ten boxes with the exploding ones coloured in.
+    Early,
+    /// Ages 8-10: "3 in 10".
+    #[default]
+    Middle,
+    /// Ages 11 and up: percentages and expected values with decimals.
+    Older,
+}
+
+impl AgeGroup {
+    pub fn label_key(self) -> &'static str {
+        match self {
+            AgeGroup::Early => "tutor.age.early",
+            AgeGroup::Middle => "tutor.age.middle",
+            AgeGroup::Older => "tutor.age.older",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            AgeGroup::Early => AgeGroup::Middle,
+            AgeGroup::Middle => AgeGroup::Older,
+            AgeGroup::Older => AgeGroup::Early,
+        }
+    }
+}
+
+/// Whether the tutor explains crate odds during a round, and for which age group.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TutorSettings {
+    pub enabled: bool,
+    pub age_group: AgeGroup,
+}
@@ -0,0 +1,68 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::scene::ScenePlugin;
+use bevy::state::app::StatesPlugin;
+use std::time::{Duration, Instant};
+use wacky_crates::{GameRunnerPlugin, GameState, GameplayEvent, HeadlessPlugin, Platform, Player};
+
+/// Gameplay events an embedding app saw, in order.
+#[derive(Resource, Default)]
+struct Seen(Vec<GameplayEvent>);
+
+fn collect_events(mut events: EventReader<GameplayEvent>, mut seen: ResMut<Seen>) {
+    seen.0.extend(events.read().cloned());
+}
+
+#[test]
+fn embedding_app_sees_the_round() {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .init_resource::<Seen>()
+        .add_systems(Update, collect_events);
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while *app.world().resource::<State<GameState>>().get() != GameState::InGame {
+        assert!(
+            Instant::now() < deadline,
+            "the level never finished loading"
+        );
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    for _ in 0..120 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    assert!(world.query::<&Platform>().iter(world).count() > 0);
+    let landed = world.resource::<Seen>().0.iter().any(
+        |event| matches!(event, GameplayEvent::Landed { player: landed, .. } if *landed == player),
+    );
+    assert!(landed, "the player's landing wasn't reported");
+}
+
+#[test]
+fn game_runner_plugin_works_on_its_own() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        TransformPlugin,
+        HierarchyPlugin,
+        AssetPlugin::default(),
+        InputPlugin,
+        ScenePlugin,
+        GameRunnerPlugin,
+    ))
+    .init_resource::<Seen>()
+    .add_systems(Update, collect_events);
+    for _ in 0..10 {
+        app.update();
+    }
+
+    // The app starts in the menu, where no gameplay runs
+    let state = app.world().resource::<State<GameState>>();
+    assert_eq!(*state.get(), GameState::MainMenu);
+    assert!(app.world().resource::<Seen>().0.is_empty());
+}
@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use std::time::{Duration, Instant};
+use wacky_crates::{
+    GameState, HeadlessPlugin, MoveDirection, Player, PlayerInputs, PlayerState, PlayerStateKind,
+    PlayerStatus,
+};
+
+/// Where the player stands on the Classic level: the top of the red platform plus half
+/// the player's 128 px frame.
+const GROUND_Y: f32 = -140.0 + 64.0;
+
+/// A headless app that has loaded the level and started the round.
+fn start_round() -> App {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default());
+    // The level loads on the IO task pool, so give it real time rather than a frame count
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while *app.world().resource::<State<GameState>>().get() != GameState::InGame {
+        assert!(
+            Instant::now() < deadline,
+            "the level never finished loading"
+        );
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    app
+}
+
+fn step(app: &mut App, frames: u32) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+/// Position, state, score and pot of the player.
+struct Snapshot {
+    position: Vec3,
+    state: PlayerStateKind,
+    hp: u32,
+    points: u32,
+    pot: u32,
+}
+
+fn player(app: &mut App) -> Snapshot {
+    let (transform, state, status) = app
+        .world_mut()
+        .query_filtered::<(&Transform, &PlayerState, &PlayerStatus), With<Player>>()
+        .single(app.world());
+    Snapshot {
+        position: transform.translation,
+        state: state.current_state(),
+        hp: status.hp,
+        points: status.points,
+        pot: status.pot,
+    }
+}
+
+#[test]
+fn player_lands_on_the_platform() {
+    let mut app = start_round();
+    step(&mut app, 120);
+
+    let player = player(&mut app);
+    assert_eq!(player.state, PlayerStateKind::Idle);
+    assert_eq!(player.position.x, 0.0);
+    assert!(
+        (player.position.y - GROUND_Y).abs() < 1.0,
+        "player at {}",
+        player.position
+    );
+    assert_eq!(player.hp, 100);
+    assert_eq!(player.points, 0);
+    assert_eq!(player.pot, 0);
+}
+
+#[test]
+fn walking_moves_the_player() {
+    let mut app = start_round();
+    step(&mut app, 60);
+
+    app.world_mut()
+        .send_event(PlayerInputs::Walk(MoveDirection::Right));
+    step(&mut app, 30);
+
+    let player = player(&mut app);
+    assert!(
+        matches!(player.state, PlayerStateKind::Walk(_)),
+        "state {:?}",
+        player.state
+    );
+    assert!(player.position.x > 0.0, "player at {}", player.position);
+}
+
+#[test]
+fn attacking_starts_the_attack() {
+    let mut app = start_round();
+    step(&mut app, 60);
+
+    app.world_mut().send_event(PlayerInputs::Attack);
+    step(&mut app, 2);
+
+    assert_eq!(player(&mut app).state, PlayerStateKind::Attack);
+}
@@ -0,0 +1,32 @@
+use std::process::Command;
+
+/// Runs the game binary with `--headless` and returns the line describing the player.
+fn run_headless(frames: u32) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_WackyCrates"))
+        .args(["--headless", "--frames", &frames.to_string()])
+        .output()
+        .expect("failed to start the game");
+    assert!(
+        output.status.success(),
+        "headless run failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.starts_with("Player at"))
+        .expect("no player in the output")
+        .to_string()
+}
+
+#[test]
+fn player_lands_on_the_platform() {
+    let player = run_headless(300);
+
+    // The Classic level starts the player at the origin, above the red platform whose top
+    // is at y = -140; the player's 128 px frame stands on it.
+    assert!(
+        player.starts_with("Player at Vec2(0.0, -76.0), state Idle,"),
+        "{player}"
+    );
+    assert!(player.ends_with("hp 100, points 0, pot 0"), "{player}");
+}